@@ -0,0 +1,95 @@
+//! Alerts shown by flashing the clock area.
+//!
+//! Any widget can raise an alert (alarms, finished timers, ...). The oldest
+//! active alert takes over the clock until it is dismissed with `accept` or
+//! snoozed with `cancel`.
+
+use chrono::{DateTime, Duration, Local};
+use crossterm::event::{KeyEvent, KeyEventKind};
+use std::{collections::VecDeque, sync::Mutex};
+
+use crate::config::CONFIG;
+
+#[derive(Debug, Clone)]
+pub struct Alert {
+    pub title: String,
+    /// text shown in big digits instead of the current time
+    pub digits: Option<String>,
+    pub raised_at: DateTime<Local>,
+}
+
+struct AlertState {
+    active: VecDeque<Alert>,
+    snoozed: Vec<(DateTime<Local>, Alert)>,
+}
+
+static ALERTS: Mutex<AlertState> = Mutex::new(AlertState {
+    active: VecDeque::new(),
+    snoozed: Vec::new(),
+});
+
+/// Queues an alert; it is shown once the ones before it are handled.
+pub fn raise(title: &str) {
+    push(title, None);
+}
+
+/// Queues an alert that also replaces the clock digits with `digits`.
+pub fn raise_with_digits(title: &str, digits: &str) {
+    push(title, Some(digits.to_string()));
+}
+
+fn push(title: &str, digits: Option<String>) {
+    let mut state = ALERTS.lock().unwrap();
+    state.active.push_back(Alert {
+        title: title.to_string(),
+        digits,
+        raised_at: Local::now(),
+    });
+}
+
+/// The alert currently shown on the clock, if any.
+pub fn active() -> Option<Alert> {
+    ALERTS.lock().unwrap().active.front().cloned()
+}
+
+/// Re-raises snoozed alerts that are due. Called from the main loop tick.
+pub fn tick(now: DateTime<Local>) {
+    let mut state = ALERTS.lock().unwrap();
+    let (due, waiting): (Vec<_>, Vec<_>) = state.snoozed.drain(..).partition(|(at, _)| *at <= now);
+    state.snoozed = waiting;
+
+    for (_, mut alert) in due {
+        alert.raised_at = now;
+        state.active.push_back(alert);
+    }
+}
+
+/// Dismisses or snoozes the active alert. Returns `true` if the key was used.
+pub fn handle_key_event(key_event: &KeyEvent) -> bool {
+    if key_event.kind != KeyEventKind::Press || active().is_none() {
+        return false;
+    }
+
+    let c = CONFIG();
+
+    if c.key_matches(key_event, &c.keybinds.accept) {
+        dismiss();
+        true
+    } else if c.key_matches(key_event, &c.keybinds.cancel) {
+        snooze(Duration::minutes(c.alarms.snooze_minutes as i64));
+        true
+    } else {
+        false
+    }
+}
+
+fn dismiss() {
+    ALERTS.lock().unwrap().active.pop_front();
+}
+
+fn snooze(duration: Duration) {
+    let mut state = ALERTS.lock().unwrap();
+    if let Some(alert) = state.active.pop_front() {
+        state.snoozed.push((Local::now() + duration, alert));
+    }
+}
@@ -0,0 +1,198 @@
+//! Saved audio profiles: named snapshots of per-application volumes and mute
+//! states, stored in `~/.config/aex/audio_profiles.toml`.
+//!
+//! A profile is applied to the streams playing at that moment, matching them
+//! by application name. Streams without an entry are left alone.
+
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, eyre},
+};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::{
+    backend::audio::{self, AudioBackend, SinkInput},
+    config::{VOLUME_LIMIT, aex_config_dir},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioProfile {
+    pub name: String,
+    #[serde(default)]
+    pub streams: Vec<ProfileStream>,
+}
+
+/// Volume and mute state of one application.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileStream {
+    pub application: String,
+    pub volume: u8,
+    #[serde(default)]
+    pub muted: bool,
+}
+
+// ProfileFile mirrors `audio_profiles.toml`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfileFile {
+    #[serde(default)]
+    profiles: Vec<AudioProfile>,
+}
+
+impl AudioProfile {
+    /// Snapshot of the given streams. When an application has several
+    /// streams, the first one is saved.
+    pub fn capture(name: &str, streams: &[SinkInput]) -> Self {
+        let mut saved: Vec<ProfileStream> = Vec::new();
+
+        for stream in streams {
+            if saved.iter().any(|s| s.application == stream.name) {
+                continue;
+            }
+            saved.push(ProfileStream {
+                application: stream.name.clone(),
+                volume: audio::overall_volume(&stream.channels),
+                muted: stream.muted,
+            });
+        }
+
+        Self {
+            name: name.to_string(),
+            streams: saved,
+        }
+    }
+
+    /// Apply the profile to the running streams and return how many matched.
+    /// Volumes are capped at `max_volume` and scaled so each stream keeps its
+    /// current balance. A stream that fails, e.g. because it just ended, does
+    /// not stop the others; the failures are reported together afterwards.
+    pub fn apply(&self, backend: &dyn AudioBackend, max_volume: u8) -> Result<usize> {
+        let mut applied = 0;
+        let mut failures: Vec<String> = Vec::new();
+
+        for stream in backend.sink_inputs()? {
+            let Some(saved) = self
+                .streams
+                .iter()
+                .find(|s| s.application.eq_ignore_ascii_case(&stream.name))
+            else {
+                continue;
+            };
+
+            let volumes = audio::scale_channels(&stream.channels, saved.volume.min(max_volume));
+            let result = backend
+                .set_sink_input_volume(&stream.id, &volumes)
+                .and_then(|()| backend.set_sink_input_mute(&stream.id, saved.muted));
+            match result {
+                Ok(()) => applied += 1,
+                Err(e) => failures.push(format!("{}: {}", stream.name, e)),
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(eyre!(
+                "Applied profile {} to {} stream(s), failed for {}",
+                self.name,
+                applied,
+                failures.join("; ")
+            ));
+        }
+        Ok(applied)
+    }
+}
+
+// ====== Storage ======
+
+fn file_path() -> Result<PathBuf> {
+    let mut path = aex_config_dir()?;
+    path.push("audio_profiles.toml");
+    Ok(path)
+}
+
+/// Load the saved profiles; a missing file means no profiles.
+pub fn load() -> Result<Vec<AudioProfile>> {
+    let path = file_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let profiles_str = fs::read_to_string(&path)?;
+    let file: ProfileFile = toml::from_str(&profiles_str)
+        .wrap_err_with(|| format!("Failed to parse {}", path.display()))?;
+    validate(&file.profiles).wrap_err_with(|| format!("Invalid {}", path.display()))?;
+    Ok(file.profiles)
+}
+
+/// Rejects volumes above `VOLUME_LIMIT`, as a hand-edited file could ask for any `u8`.
+fn validate(profiles: &[AudioProfile]) -> Result<()> {
+    for profile in profiles {
+        for stream in &profile.streams {
+            if stream.volume > VOLUME_LIMIT {
+                return Err(eyre!(
+                    "Volume of {} in profile {} must be at most {}, got {}",
+                    stream.application,
+                    profile.name,
+                    VOLUME_LIMIT,
+                    stream.volume
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn save(profiles: &[AudioProfile]) -> Result<()> {
+    let path = file_path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let file = ProfileFile {
+        profiles: profiles.to_vec(),
+    };
+    fs::write(&path, toml::to_string(&file)?)?;
+    Ok(())
+}
+
+/// Load the profile called `name` and apply it; used by `--profile`.
+pub fn apply_by_name(name: &str, backend: &dyn AudioBackend, max_volume: u8) -> Result<usize> {
+    let profiles = load()?;
+    let profile = profiles
+        .iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| eyre!("No audio profile named {}", name))?;
+    profile.apply(backend, max_volume)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::audio::FakeAudioBackend;
+
+    fn profile(volume: u8) -> AudioProfile {
+        AudioProfile {
+            name: "loud".to_string(),
+            streams: vec![ProfileStream {
+                application: "spotify".to_string(),
+                volume,
+                muted: true,
+            }],
+        }
+    }
+
+    #[test]
+    fn apply_caps_volumes_at_max_volume() {
+        let backend = FakeAudioBackend::default();
+        assert_eq!(profile(140).apply(&backend, 100).unwrap(), 1);
+
+        let spotify = &backend.sink_inputs().unwrap()[1];
+        assert_eq!(audio::overall_volume(&spotify.channels), 100);
+        assert!(spotify.muted);
+    }
+
+    #[test]
+    fn volumes_above_the_limit_are_rejected() {
+        assert!(validate(&[profile(VOLUME_LIMIT)]).is_ok());
+        assert!(validate(&[profile(VOLUME_LIMIT + 1)]).is_err());
+    }
+}
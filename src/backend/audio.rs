@@ -0,0 +1,418 @@
+//! Audio backend: lists output and input devices and application streams and
+//! changes their volume and mute state.
+//!
+//! `PactlBackend` (in `backend::pactl`) runs `pactl`; `FakeAudioBackend`
+//! keeps streams in memory. Set `AEX_AUDIO_BACKEND=fake` to use the fake one.
+
+use color_eyre::{Result, eyre::eyre};
+use std::{
+    env,
+    sync::{Arc, Mutex},
+};
+
+use crate::backend::pactl::PactlBackend;
+
+/// Volume of one channel, in percent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelVolume {
+    pub channel: String,
+    pub percent: u8,
+}
+
+/// An application stream.
+#[derive(Debug, Clone)]
+pub struct SinkInput {
+    pub id: String,
+    /// application name, or the best fallback the stream offers
+    pub name: String,
+    pub media_name: Option<String>,
+    pub binary: Option<String>,
+    pub pid: Option<u32>,
+    /// index of the sink the stream plays on
+    pub sink: String,
+    pub channels: Vec<ChannelVolume>,
+    pub muted: bool,
+}
+
+/// An output (sink) or input (source) device.
+#[derive(Debug, Clone)]
+pub struct AudioDevice {
+    pub index: String,
+    pub name: String,
+    pub description: String,
+    pub channels: Vec<ChannelVolume>,
+    pub muted: bool,
+}
+
+// ====== Channels ======
+
+/// Overall volume, the loudest channel like pactl reports it.
+pub fn overall_volume(channels: &[ChannelVolume]) -> u8 {
+    channels.iter().map(|c| c.percent).max().unwrap_or(0)
+}
+
+fn is_left(channel: &ChannelVolume) -> bool {
+    channel.channel.contains("left")
+}
+
+fn is_right(channel: &ChannelVolume) -> bool {
+    channel.channel.contains("right")
+}
+
+/// Loudest left and loudest right channel, or `None` for channel maps without
+/// both sides (e.g. mono).
+pub fn side_volumes(channels: &[ChannelVolume]) -> Option<(u8, u8)> {
+    let side = |is_side: fn(&ChannelVolume) -> bool| {
+        channels
+            .iter()
+            .filter(|c| is_side(c))
+            .map(|c| c.percent)
+            .max()
+    };
+
+    Some((side(is_left)?, side(is_right)?))
+}
+
+/// Balance from -100 (left only) through 0 (centered) to 100 (right only).
+pub fn balance(channels: &[ChannelVolume]) -> i32 {
+    match side_volumes(channels) {
+        Some((left, right)) if left > right => -(100 - right as i32 * 100 / left as i32),
+        Some((left, right)) if right > left => 100 - left as i32 * 100 / right as i32,
+        _ => 0,
+    }
+}
+
+/// Channel volumes with the given balance, keeping the overall volume: the
+/// side the balance leans to stays at full volume and the other one is lowered.
+pub fn balance_channels(channels: &[ChannelVolume], balance: i32) -> Vec<u8> {
+    let volume = overall_volume(channels) as i32;
+    let balance = balance.clamp(-100, 100);
+    let left = volume * (100 - balance.max(0)) / 100;
+    let right = volume * (100 + balance.min(0)) / 100;
+
+    channels
+        .iter()
+        .map(|c| {
+            if is_left(c) {
+                left as u8
+            } else if is_right(c) {
+                right as u8
+            } else {
+                volume as u8
+            }
+        })
+        .collect()
+}
+
+/// Channel volumes scaled so the loudest one is at `volume`, keeping the balance.
+pub fn scale_channels(channels: &[ChannelVolume], volume: u8) -> Vec<u8> {
+    let current = overall_volume(channels) as u32;
+
+    channels
+        .iter()
+        .map(|c| {
+            (c.percent as u32 * volume as u32 + current / 2)
+                .checked_div(current)
+                .map_or(volume, |v| v as u8)
+        })
+        .collect()
+}
+
+pub trait AudioBackend: Send + Sync {
+    /// Lists the current application streams.
+    fn sink_inputs(&self) -> Result<Vec<SinkInput>>;
+
+    /// Sets the volume of a stream, in percent. `volumes` has one value per
+    /// channel, or a single value for all channels; the same goes for devices.
+    fn set_sink_input_volume(&self, id: &str, volumes: &[u8]) -> Result<()>;
+
+    fn set_sink_input_mute(&self, id: &str, muted: bool) -> Result<()>;
+
+    /// Moves a stream to another output device.
+    fn move_sink_input(&self, id: &str, sink: &str) -> Result<()>;
+
+    /// Lists the output devices.
+    fn sinks(&self) -> Result<Vec<AudioDevice>>;
+
+    /// Name of the sink new streams play on.
+    fn default_sink(&self) -> Result<String>;
+
+    fn set_default_sink(&self, name: &str) -> Result<()>;
+
+    /// Sets the volume of an output device, in percent.
+    fn set_sink_volume(&self, name: &str, volumes: &[u8]) -> Result<()>;
+
+    fn set_sink_mute(&self, name: &str, muted: bool) -> Result<()>;
+
+    /// Lists the capture devices, without monitors of outputs.
+    fn sources(&self) -> Result<Vec<AudioDevice>>;
+
+    /// Name of the source new recordings use.
+    fn default_source(&self) -> Result<String>;
+
+    fn set_default_source(&self, name: &str) -> Result<()>;
+
+    /// Sets the volume of a capture device, in percent.
+    fn set_source_volume(&self, name: &str, volumes: &[u8]) -> Result<()>;
+
+    fn set_source_mute(&self, name: &str, muted: bool) -> Result<()>;
+
+    /// Blocks and calls `on_change` whenever a stream, device or default
+    /// device changes, until `on_change` returns `false` or the events end.
+    /// Backends without change events return an error, and callers poll.
+    fn subscribe(&self, _on_change: &mut dyn FnMut() -> bool) -> Result<()> {
+        Err(eyre!("Change events are not supported"))
+    }
+}
+
+/// Picks the backend from `AEX_AUDIO_BACKEND` (`pactl` by default, or `fake`).
+pub fn from_env() -> Arc<dyn AudioBackend> {
+    match env::var("AEX_AUDIO_BACKEND").as_deref() {
+        Ok("fake") => Arc::new(FakeAudioBackend::default()),
+        _ => Arc::new(PactlBackend::new()),
+    }
+}
+
+// ====== Fake ======
+
+/// State kept by `FakeAudioBackend`.
+pub struct FakeAudioState {
+    pub sinks: Vec<AudioDevice>,
+    pub default_sink: String,
+    pub sources: Vec<AudioDevice>,
+    pub default_source: String,
+    pub inputs: Vec<SinkInput>,
+}
+
+/// In-memory backend for machines without PulseAudio.
+pub struct FakeAudioBackend {
+    state: Mutex<FakeAudioState>,
+}
+
+impl FakeAudioBackend {
+    pub fn new(state: FakeAudioState) -> Self {
+        Self {
+            state: Mutex::new(state),
+        }
+    }
+
+    fn with_sink<T>(&self, name: &str, f: impl FnOnce(&mut AudioDevice) -> T) -> Result<T> {
+        let mut state = self.state.lock().unwrap();
+        let sink = state
+            .sinks
+            .iter_mut()
+            .find(|sink| sink.name == name)
+            .ok_or_else(|| eyre!("No sink {}", name))?;
+        Ok(f(sink))
+    }
+
+    fn with_input<T>(&self, id: &str, f: impl FnOnce(&mut SinkInput) -> T) -> Result<T> {
+        let mut state = self.state.lock().unwrap();
+        let input = state
+            .inputs
+            .iter_mut()
+            .find(|input| input.id == id)
+            .ok_or_else(|| eyre!("No sink input #{}", id))?;
+        Ok(f(input))
+    }
+
+    fn with_source<T>(&self, name: &str, f: impl FnOnce(&mut AudioDevice) -> T) -> Result<T> {
+        let mut state = self.state.lock().unwrap();
+        let source = state
+            .sources
+            .iter_mut()
+            .find(|source| source.name == name)
+            .ok_or_else(|| eyre!("No source {}", name))?;
+        Ok(f(source))
+    }
+}
+
+/// Applies `volumes` like `pactl` does: a single value sets every channel.
+fn set_channels(channels: &mut [ChannelVolume], volumes: &[u8]) {
+    match volumes {
+        [volume] => channels.iter_mut().for_each(|c| c.percent = *volume),
+        _ => channels
+            .iter_mut()
+            .zip(volumes)
+            .for_each(|(c, volume)| c.percent = *volume),
+    }
+}
+
+fn stereo(volume: u8) -> Vec<ChannelVolume> {
+    ["front-left", "front-right"]
+        .into_iter()
+        .map(|channel| ChannelVolume {
+            channel: channel.into(),
+            percent: volume,
+        })
+        .collect()
+}
+
+impl Default for FakeAudioBackend {
+    fn default() -> Self {
+        let device = |index: &str, name: &str, description: &str, volume| AudioDevice {
+            index: index.into(),
+            name: name.into(),
+            description: description.into(),
+            channels: stereo(volume),
+            muted: false,
+        };
+        let input = |id: &str, name: &str, binary: &str, volume| SinkInput {
+            id: id.into(),
+            name: name.into(),
+            media_name: None,
+            binary: Some(binary.into()),
+            pid: None,
+            sink: "0".into(),
+            channels: stereo(volume),
+            muted: false,
+        };
+
+        Self::new(FakeAudioState {
+            sinks: vec![
+                device("0", "speakers", "Built-in Audio Analog Stereo", 70),
+                device("1", "headset", "USB Headset", 45),
+                device("2", "hdmi", "HDMI / DisplayPort", 100),
+            ],
+            default_sink: "speakers".into(),
+            sources: vec![
+                device("3", "mic", "Built-in Microphone", 80),
+                device("4", "headset-mic", "USB Headset Microphone", 60),
+            ],
+            default_source: "mic".into(),
+            inputs: vec![
+                input("1", "Firefox", "firefox", 60),
+                input("2", "Spotify", "spotify", 35),
+                input("3", "Discord", "Discord", 100),
+            ],
+        })
+    }
+}
+
+impl AudioBackend for FakeAudioBackend {
+    fn sink_inputs(&self) -> Result<Vec<SinkInput>> {
+        Ok(self.state.lock().unwrap().inputs.clone())
+    }
+
+    fn set_sink_input_volume(&self, id: &str, volumes: &[u8]) -> Result<()> {
+        self.with_input(id, |input| set_channels(&mut input.channels, volumes))
+    }
+
+    fn set_sink_input_mute(&self, id: &str, muted: bool) -> Result<()> {
+        self.with_input(id, |input| input.muted = muted)
+    }
+
+    fn move_sink_input(&self, id: &str, sink: &str) -> Result<()> {
+        let index = self.with_sink(sink, |sink| sink.index.clone())?;
+        self.with_input(id, |input| input.sink = index)
+    }
+
+    fn sinks(&self) -> Result<Vec<AudioDevice>> {
+        Ok(self.state.lock().unwrap().sinks.clone())
+    }
+
+    fn default_sink(&self) -> Result<String> {
+        Ok(self.state.lock().unwrap().default_sink.clone())
+    }
+
+    fn set_default_sink(&self, name: &str) -> Result<()> {
+        self.with_sink(name, |_| ())?;
+        self.state.lock().unwrap().default_sink = name.to_string();
+        Ok(())
+    }
+
+    fn set_sink_volume(&self, name: &str, volumes: &[u8]) -> Result<()> {
+        self.with_sink(name, |sink| set_channels(&mut sink.channels, volumes))
+    }
+
+    fn set_sink_mute(&self, name: &str, muted: bool) -> Result<()> {
+        self.with_sink(name, |sink| sink.muted = muted)
+    }
+
+    fn sources(&self) -> Result<Vec<AudioDevice>> {
+        Ok(self.state.lock().unwrap().sources.clone())
+    }
+
+    fn default_source(&self) -> Result<String> {
+        Ok(self.state.lock().unwrap().default_source.clone())
+    }
+
+    fn set_default_source(&self, name: &str) -> Result<()> {
+        self.with_source(name, |_| ())?;
+        self.state.lock().unwrap().default_source = name.to_string();
+        Ok(())
+    }
+
+    fn set_source_volume(&self, name: &str, volumes: &[u8]) -> Result<()> {
+        self.with_source(name, |source| set_channels(&mut source.channels, volumes))
+    }
+
+    fn set_source_mute(&self, name: &str, muted: bool) -> Result<()> {
+        self.with_source(name, |source| source.muted = muted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channels(volumes: &[(&str, u8)]) -> Vec<ChannelVolume> {
+        volumes
+            .iter()
+            .map(|(channel, percent)| ChannelVolume {
+                channel: channel.to_string(),
+                percent: *percent,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn balance_round_trips_at_full_volume() {
+        let centered = stereo(100);
+        for balance_value in (-100..=100).step_by(5) {
+            let volumes = balance_channels(&centered, balance_value);
+            let mut balanced = centered.clone();
+            set_channels(&mut balanced, &volumes);
+            assert_eq!(balance(&balanced), balance_value);
+        }
+    }
+
+    #[test]
+    fn balance_round_trips_through_fake_backend() {
+        let backend = FakeAudioBackend::default();
+        let original = backend.sink_inputs().unwrap()[1].channels.clone();
+
+        for balance_value in [-60, -5, 0, 25, 100] {
+            let volumes = balance_channels(&original, balance_value);
+            backend.set_sink_input_volume("2", &volumes).unwrap();
+
+            let channels = &backend.sink_inputs().unwrap()[1].channels;
+            // 35% leaves little room, so allow integer rounding
+            assert!((balance(channels) - balance_value).abs() <= 2);
+            assert_eq!(overall_volume(channels), overall_volume(&original));
+        }
+    }
+
+    #[test]
+    fn balance_of_mono_and_centered_channels_is_zero() {
+        let mono = channels(&[("mono", 70)]);
+        assert_eq!(side_volumes(&mono), None);
+        assert_eq!(balance(&mono), 0);
+        assert_eq!(balance_channels(&mono, 50), vec![70]);
+        assert_eq!(balance(&stereo(40)), 0);
+    }
+
+    #[test]
+    fn balance_keeps_channels_without_a_side() {
+        let surround = channels(&[("front-left", 80), ("front-right", 80), ("lfe", 80)]);
+        assert_eq!(balance_channels(&surround, -50), vec![80, 40, 80]);
+    }
+
+    #[test]
+    fn scale_channels_keeps_the_ratio() {
+        let leaning = channels(&[("front-left", 80), ("front-right", 40)]);
+        assert_eq!(scale_channels(&leaning, 100), vec![100, 50]);
+        assert_eq!(scale_channels(&leaning, 0), vec![0, 0]);
+        assert_eq!(scale_channels(&stereo(0), 30), vec![30, 30]);
+    }
+}
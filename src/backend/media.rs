@@ -0,0 +1,214 @@
+//! Media backend: lists MPRIS media players and controls their playback.
+//!
+//! `MprisBackend` (in `backend::mpris`) talks to the session bus through
+//! `busctl`; `FakeMediaBackend` keeps players in memory. Set
+//! `AEX_MEDIA_BACKEND=fake` to use the fake one.
+
+use color_eyre::{Result, eyre::eyre};
+use std::{
+    env,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::backend::mpris::MprisBackend;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaybackStatus {
+    Playing,
+    Paused,
+    Stopped,
+}
+
+/// A media player and its current track.
+#[derive(Debug, Clone)]
+pub struct Player {
+    /// bus name, e.g. `org.mpris.MediaPlayer2.spotify`
+    pub bus_name: String,
+    /// name the player gives itself, e.g. `Spotify`
+    pub identity: String,
+    pub status: PlaybackStatus,
+    pub title: Option<String>,
+    /// artists joined with `, `
+    pub artist: Option<String>,
+    pub position: Option<Duration>,
+    pub length: Option<Duration>,
+}
+
+pub trait MediaBackend: Send + Sync {
+    /// Lists the running players.
+    fn players(&self) -> Result<Vec<Player>>;
+
+    fn play_pause(&self, bus_name: &str) -> Result<()>;
+
+    fn next(&self, bus_name: &str) -> Result<()>;
+
+    fn previous(&self, bus_name: &str) -> Result<()>;
+
+    /// Moves the position by `offset_us` microseconds, backwards when negative.
+    fn seek(&self, bus_name: &str, offset_us: i64) -> Result<()>;
+}
+
+/// Picks the backend from `AEX_MEDIA_BACKEND` (`mpris` by default, or `fake`).
+pub fn from_env() -> Arc<dyn MediaBackend> {
+    match env::var("AEX_MEDIA_BACKEND").as_deref() {
+        Ok("fake") => Arc::new(FakeMediaBackend::default()),
+        _ => Arc::new(MprisBackend),
+    }
+}
+
+// ====== Fake ======
+
+/// A player of `FakeMediaBackend` with a fixed playlist.
+pub struct FakePlayer {
+    pub player: Player,
+    /// (title, artist, length) of each track
+    pub tracks: Vec<(String, String, Duration)>,
+    pub track: usize,
+    /// when `player.position` was last updated, to advance it while playing
+    pub since: Instant,
+}
+
+impl FakePlayer {
+    fn new(name: &str, identity: &str, tracks: &[(&str, &str, u64)]) -> Self {
+        let mut player = FakePlayer {
+            player: Player {
+                bus_name: format!("org.mpris.MediaPlayer2.{}", name),
+                identity: identity.into(),
+                status: PlaybackStatus::Paused,
+                title: None,
+                artist: None,
+                position: Some(Duration::ZERO),
+                length: None,
+            },
+            tracks: tracks
+                .iter()
+                .map(|(title, artist, secs)| {
+                    (
+                        title.to_string(),
+                        artist.to_string(),
+                        Duration::from_secs(*secs),
+                    )
+                })
+                .collect(),
+            track: 0,
+            since: Instant::now(),
+        };
+        player.load_track(0);
+        player
+    }
+
+    fn load_track(&mut self, track: usize) {
+        self.track = track % self.tracks.len();
+        let (title, artist, length) = &self.tracks[self.track];
+        self.player.title = Some(title.clone());
+        self.player.artist = Some(artist.clone());
+        self.player.length = Some(*length);
+        self.player.position = Some(Duration::ZERO);
+        self.since = Instant::now();
+    }
+
+    /// Position now, advanced by the time played since the last update.
+    fn position(&self) -> Duration {
+        let position = self.player.position.unwrap_or_default();
+        let played = if self.player.status == PlaybackStatus::Playing {
+            self.since.elapsed()
+        } else {
+            Duration::ZERO
+        };
+        (position + played).min(self.player.length.unwrap_or(Duration::MAX))
+    }
+
+    fn set_position(&mut self, position: Duration) {
+        self.player.position = Some(position);
+        self.since = Instant::now();
+    }
+}
+
+/// In-memory backend for machines without a session bus.
+pub struct FakeMediaBackend {
+    players: Mutex<Vec<FakePlayer>>,
+}
+
+impl FakeMediaBackend {
+    pub fn new(players: Vec<FakePlayer>) -> Self {
+        Self {
+            players: Mutex::new(players),
+        }
+    }
+
+    fn with_player<T>(&self, bus_name: &str, f: impl FnOnce(&mut FakePlayer) -> T) -> Result<T> {
+        let mut players = self.players.lock().unwrap();
+        let player = players
+            .iter_mut()
+            .find(|p| p.player.bus_name == bus_name)
+            .ok_or_else(|| eyre!("No player {}", bus_name))?;
+        Ok(f(player))
+    }
+}
+
+impl Default for FakeMediaBackend {
+    fn default() -> Self {
+        let mut spotify = FakePlayer::new(
+            "spotify",
+            "Spotify",
+            &[
+                ("Windowlicker", "Aphex Twin", 366),
+                ("Teardrop", "Massive Attack", 329),
+                ("Roygbiv", "Boards of Canada", 151),
+            ],
+        );
+        spotify.player.status = PlaybackStatus::Playing;
+
+        let firefox = FakePlayer::new(
+            "firefox.instance_1_42",
+            "Mozilla Firefox",
+            &[("Lecture 4: Ownership", "Rust Course", 2843)],
+        );
+
+        Self::new(vec![spotify, firefox])
+    }
+}
+
+impl MediaBackend for FakeMediaBackend {
+    fn players(&self) -> Result<Vec<Player>> {
+        Ok(self
+            .players
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|p| Player {
+                position: Some(p.position()),
+                ..p.player.clone()
+            })
+            .collect())
+    }
+
+    fn play_pause(&self, bus_name: &str) -> Result<()> {
+        self.with_player(bus_name, |p| {
+            let position = p.position();
+            p.player.status = match p.player.status {
+                PlaybackStatus::Playing => PlaybackStatus::Paused,
+                _ => PlaybackStatus::Playing,
+            };
+            p.set_position(position);
+        })
+    }
+
+    fn next(&self, bus_name: &str) -> Result<()> {
+        self.with_player(bus_name, |p| p.load_track(p.track + 1))
+    }
+
+    fn previous(&self, bus_name: &str) -> Result<()> {
+        self.with_player(bus_name, |p| p.load_track(p.track + p.tracks.len() - 1))
+    }
+
+    fn seek(&self, bus_name: &str, offset_us: i64) -> Result<()> {
+        self.with_player(bus_name, |p| {
+            let position = p.position().as_micros() as i64 + offset_us;
+            let length = p.player.length.unwrap_or_default().as_micros() as i64;
+            let position = position.clamp(0, length) as u64;
+            p.set_position(Duration::from_micros(position));
+        })
+    }
+}
@@ -0,0 +1,13 @@
+//! System backends used by the widgets.
+//!
+//! Widgets talk to these traits instead of running commands directly, so a
+//! fake implementation can stand in where the real tools are missing.
+
+pub mod audio;
+pub mod media;
+pub mod mpris;
+pub mod network;
+pub mod pactl;
+
+#[cfg(test)]
+mod test_bus;
@@ -0,0 +1,261 @@
+//! `MediaBackend` implementation that talks to MPRIS players on the session
+//! bus by running `busctl --user --json=short`.
+
+use color_eyre::{Result, eyre::eyre};
+use serde_json::Value;
+use std::{process::Command, time::Duration};
+
+use crate::backend::media::{MediaBackend, PlaybackStatus, Player};
+
+const BUS_NAME_PREFIX: &str = "org.mpris.MediaPlayer2.";
+const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+const ROOT_INTERFACE: &str = "org.mpris.MediaPlayer2";
+const PLAYER_INTERFACE: &str = "org.mpris.MediaPlayer2.Player";
+
+pub struct MprisBackend;
+
+impl MprisBackend {
+    /// Runs busctl and returns the `data` of every JSON line it prints.
+    fn busctl(args: &[&str]) -> Result<Vec<Value>> {
+        let output = Command::new("busctl")
+            .args(["--user", "--json=short"])
+            .args(args)
+            .output()
+            .map_err(|e| eyre!("Failed to run busctl: {}", e))?;
+
+        if !output.status.success() {
+            return Err(eyre!(
+                "busctl {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let mut value: Value = serde_json::from_str(line)?;
+                Ok(value["data"].take())
+            })
+            .collect()
+    }
+
+    /// Calls a method of the player interface; `--` keeps negative numbers
+    /// from being read as options.
+    fn call(bus_name: &str, method: &str, args: &[&str]) -> Result<()> {
+        let mut call_args = vec![
+            "call",
+            "--",
+            bus_name,
+            OBJECT_PATH,
+            PLAYER_INTERFACE,
+            method,
+        ];
+        call_args.extend(args);
+        Self::busctl(&call_args)?;
+        Ok(())
+    }
+
+    fn property(bus_name: &str, interface: &str, name: &str) -> Result<Value> {
+        Self::busctl(&["get-property", bus_name, OBJECT_PATH, interface, name])?
+            .pop()
+            .ok_or_else(|| eyre!("busctl printed no {} property", name))
+    }
+
+    fn player(bus_name: &str) -> Result<Player> {
+        let status = Self::property(bus_name, PLAYER_INTERFACE, "PlaybackStatus")?;
+        let metadata = Self::property(bus_name, PLAYER_INTERFACE, "Metadata")?;
+        // not every player reports a position
+        let position = Self::property(bus_name, PLAYER_INTERFACE, "Position")
+            .ok()
+            .and_then(|value| micros(&value));
+        let identity = Self::property(bus_name, ROOT_INTERFACE, "Identity")
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_else(|| bus_name.trim_start_matches(BUS_NAME_PREFIX).to_string());
+
+        Ok(Player {
+            bus_name: bus_name.to_string(),
+            identity,
+            status: parse_status(status.as_str().unwrap_or("")),
+            title: metadata_entry(&metadata, "xesam:title")
+                .and_then(Value::as_str)
+                .filter(|title| !title.is_empty())
+                .map(str::to_string),
+            artist: metadata_entry(&metadata, "xesam:artist")
+                .and_then(Value::as_array)
+                .map(|artists| {
+                    artists
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .filter(|artist| !artist.is_empty()),
+            position,
+            length: metadata_entry(&metadata, "mpris:length").and_then(micros),
+        })
+    }
+}
+
+impl MediaBackend for MprisBackend {
+    /// Players that stop answering while they are read are left out.
+    fn players(&self) -> Result<Vec<Player>> {
+        let names = Self::busctl(&[
+            "call",
+            "org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "ListNames",
+        ])?;
+
+        let mut bus_names: Vec<&str> = names
+            .first()
+            .and_then(|data| data.get(0))
+            .and_then(Value::as_array)
+            .map(|names| names.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        bus_names.retain(|name| name.starts_with(BUS_NAME_PREFIX));
+        bus_names.sort();
+
+        Ok(bus_names
+            .into_iter()
+            .filter_map(|name| Self::player(name).ok())
+            .collect())
+    }
+
+    fn play_pause(&self, bus_name: &str) -> Result<()> {
+        Self::call(bus_name, "PlayPause", &[])
+    }
+
+    fn next(&self, bus_name: &str) -> Result<()> {
+        Self::call(bus_name, "Next", &[])
+    }
+
+    fn previous(&self, bus_name: &str) -> Result<()> {
+        Self::call(bus_name, "Previous", &[])
+    }
+
+    fn seek(&self, bus_name: &str, offset_us: i64) -> Result<()> {
+        Self::call(bus_name, "Seek", &["x", &offset_us.to_string()])
+    }
+}
+
+// ====== Parsing ======
+
+fn parse_status(status: &str) -> PlaybackStatus {
+    match status {
+        "Playing" => PlaybackStatus::Playing,
+        "Paused" => PlaybackStatus::Paused,
+        _ => PlaybackStatus::Stopped,
+    }
+}
+
+/// The value of a metadata entry; busctl prints variants as `{"type":..,"data":..}`.
+fn metadata_entry<'a>(metadata: &'a Value, key: &str) -> Option<&'a Value> {
+    metadata.get(key).and_then(|entry| entry.get("data"))
+}
+
+/// A microsecond count, which players send as either `x` or `t`.
+fn micros(value: &Value) -> Option<Duration> {
+    value
+        .as_u64()
+        .or_else(|| value.as_i64().map(|us| us.max(0) as u64))
+        .map(Duration::from_micros)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::test_bus::{PLAYER_NAME, TestBus};
+    use std::env;
+
+    /// `data` of `busctl --user --json=short get-property org.mpris.MediaPlayer2.spotify
+    /// /org/mpris/MediaPlayer2 org.mpris.MediaPlayer2.Player Metadata`.
+    const SPOTIFY_METADATA: &str = r#"{"mpris:trackid":{"type":"o","data":"/com/spotify/track/0DiWol3AO6WpXZgp0goxAV"},"mpris:length":{"type":"t","data":320357000},"mpris:artUrl":{"type":"s","data":"https://i.scdn.co/image/ab67616d0000b273"},"xesam:album":{"type":"s","data":"Discovery"},"xesam:albumArtist":{"type":"as","data":["Daft Punk"]},"xesam:artist":{"type":"as","data":["Daft Punk","Romanthony"]},"xesam:title":{"type":"s","data":"One More Time"},"xesam:trackNumber":{"type":"i","data":1}}"#;
+
+    /// The same property from a browser tab, which sends its length as `x`.
+    const BROWSER_METADATA: &str = r#"{"mpris:trackid":{"type":"o","data":"/org/chromium/MediaPlayer2/TrackList/Track1"},"xesam:title":{"type":"s","data":"Lecture 4"},"xesam:artist":{"type":"as","data":[""]},"mpris:length":{"type":"x","data":5400000000}}"#;
+
+    #[test]
+    fn reads_metadata_entries() {
+        let metadata: Value = serde_json::from_str(SPOTIFY_METADATA).unwrap();
+
+        assert_eq!(
+            metadata_entry(&metadata, "xesam:title").and_then(Value::as_str),
+            Some("One More Time")
+        );
+        assert_eq!(
+            metadata_entry(&metadata, "xesam:artist"),
+            Some(&serde_json::json!(["Daft Punk", "Romanthony"]))
+        );
+        assert_eq!(
+            metadata_entry(&metadata, "mpris:length").and_then(micros),
+            Some(Duration::from_micros(320_357_000))
+        );
+        assert_eq!(metadata_entry(&metadata, "xesam:url"), None);
+    }
+
+    #[test]
+    fn reads_signed_lengths() {
+        let metadata: Value = serde_json::from_str(BROWSER_METADATA).unwrap();
+        assert_eq!(
+            metadata_entry(&metadata, "mpris:length").and_then(micros),
+            Some(Duration::from_secs(5400))
+        );
+    }
+
+    #[test]
+    fn parses_microseconds() {
+        // `Position` arrives as `{"type":"x","data":83000000}`
+        assert_eq!(
+            micros(&serde_json::json!(83_000_000)),
+            Some(Duration::from_secs(83))
+        );
+        // some players report a negative position before the track starts
+        assert_eq!(micros(&serde_json::json!(-1)), Some(Duration::ZERO));
+        assert_eq!(micros(&serde_json::json!("83000000")), None);
+        assert_eq!(micros(&Value::Null), None);
+    }
+
+    #[test]
+    fn parses_playback_status() {
+        assert_eq!(parse_status("Playing"), PlaybackStatus::Playing);
+        assert_eq!(parse_status("Paused"), PlaybackStatus::Paused);
+        assert_eq!(parse_status("Stopped"), PlaybackStatus::Stopped);
+        assert_eq!(parse_status(""), PlaybackStatus::Stopped);
+    }
+
+    #[test]
+    fn talks_to_a_player_on_the_session_bus() {
+        let Some(bus) = TestBus::start() else {
+            eprintln!("skipped: needs dbus-daemon and busctl");
+            return;
+        };
+        // SAFETY: only the busctl children read this variable, and the other
+        // tests reach the environment through std, which locks it
+        unsafe { env::set_var("DBUS_SESSION_BUS_ADDRESS", &bus.address) };
+
+        let players = MprisBackend.players().unwrap();
+        assert_eq!(players.len(), 1);
+        let player = &players[0];
+        assert_eq!(player.bus_name, PLAYER_NAME);
+        assert_eq!(player.identity, "Test Player");
+        assert_eq!(player.status, PlaybackStatus::Playing);
+        assert_eq!(player.title.as_deref(), Some("One More Time"));
+        assert_eq!(player.artist.as_deref(), Some("Daft Punk"));
+        assert_eq!(player.position, Some(Duration::from_secs(83)));
+        assert_eq!(player.length, Some(Duration::from_secs(320)));
+
+        // a negative offset must not be taken for a busctl option
+        MprisBackend.play_pause(PLAYER_NAME).unwrap();
+        MprisBackend.seek(PLAYER_NAME, -5_000_000).unwrap();
+        MprisBackend.seek(PLAYER_NAME, 10_000_000).unwrap();
+        assert_eq!(
+            *bus.calls.lock().unwrap(),
+            ["PlayPause", "Seek -5000000", "Seek 10000000"]
+        );
+        assert!(MprisBackend.next("org.mpris.MediaPlayer2.gone").is_err());
+    }
+}
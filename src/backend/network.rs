@@ -0,0 +1,830 @@
+//! Network backend: wifi scanning, connection state and connecting, via nmcli.
+//!
+//! Every operation goes through `NetworkBackend::nmcli`, so `NmcliBackend`
+//! runs the real tool and `FakeNmcliBackend` replays captured output. Set
+//! `AEX_NMCLI_FIXTURES=<file>` to use the fake one (see `fixtures/nmcli.txt`).
+
+use chrono::{DateTime, Local, TimeZone};
+use color_eyre::{Result, eyre::eyre};
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs,
+    path::Path,
+    process::Command,
+    sync::{Arc, Mutex},
+};
+
+/// nmcli fields shown in the connection info, with their labels.
+const DEVICE_FIELDS: [(&str, &str); 6] = [
+    ("GENERAL.DEVICE", "Device"),
+    ("GENERAL.TYPE", "Type"),
+    ("GENERAL.CONNECTION", "Name"),
+    ("IP4.ADDRESS", "IPv4"),
+    ("IP4.GATEWAY", "IPv4 Gateway"),
+    ("IP4.DNS", "DNS"),
+];
+
+/// A visible wifi network.
+#[derive(Debug, Clone)]
+pub struct WifiNetwork {
+    pub ssid: String,
+    /// signal strength in percent
+    pub signal: u8,
+    /// nmcli SECURITY field, e.g. `WPA2` or `WPA1 WPA2`; empty for open networks
+    pub security: String,
+    /// UUID of the saved connection for it, if NetworkManager has one
+    pub uuid: Option<String>,
+}
+
+impl WifiNetwork {
+    /// NetworkManager has a saved connection for it.
+    pub fn is_known(&self) -> bool {
+        self.uuid.is_some()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.security.is_empty() || self.security == "--"
+    }
+}
+
+/// Security of a network joined by hand, as its SSID is not broadcast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WifiSecurity {
+    /// WPA, WPA2 or WPA3 personal
+    Wpa,
+    Wep,
+    Open,
+}
+
+impl WifiSecurity {
+    pub const LABELS: [&'static str; 3] = ["WPA/WPA2/WPA3", "WEP", "None"];
+
+    /// The security with the given `LABELS` entry.
+    pub fn from_label(label: &str) -> Self {
+        match label {
+            "WEP" => Self::Wep,
+            "None" => Self::Open,
+            _ => Self::Wpa,
+        }
+    }
+}
+
+/// A connection profile NetworkManager has saved.
+#[derive(Debug, Clone)]
+pub struct SavedConnection {
+    pub name: String,
+    pub uuid: String,
+    /// nmcli type, e.g. `802-11-wireless` or `802-3-ethernet`
+    pub conn_type: String,
+    /// `None` when the connection was never used
+    pub last_used: Option<DateTime<Local>>,
+    pub autoconnect: bool,
+    /// higher priorities are tried first when several networks autoconnect
+    pub priority: i32,
+}
+
+impl SavedConnection {
+    pub fn is_wifi(&self) -> bool {
+        self.conn_type == "802-11-wireless"
+    }
+}
+
+/// SSIDs of saved wifi connections by UUID. A connection's SSID needs an
+/// nmcli call of its own, so each one is asked for once and kept, failed
+/// lookups included.
+#[derive(Default)]
+pub struct SsidCache(Mutex<HashMap<String, Option<String>>>);
+
+impl SsidCache {
+    /// SSID of the saved connection `uuid`; `None` when nmcli cannot tell.
+    fn get(&self, backend: &(impl NetworkBackend + ?Sized), uuid: &str) -> Option<String> {
+        self.0
+            .lock()
+            .unwrap()
+            .entry(uuid.to_string())
+            .or_insert_with(|| backend.connection_ssid(uuid).ok())
+            .clone()
+    }
+
+    /// Forgets the connections that are no longer saved.
+    fn retain(&self, saved: &[SavedConnection]) {
+        self.0
+            .lock()
+            .unwrap()
+            .retain(|uuid, _| saved.iter().any(|connection| &connection.uuid == uuid));
+    }
+}
+
+/// A wifi or ethernet device from `nmcli device show`.
+pub struct DeviceInfo {
+    pub name: String,
+    pub device_type: String,
+    /// (label, value) pairs in `DEVICE_FIELDS` order
+    pub fields: Vec<(&'static str, String)>,
+}
+
+pub trait NetworkBackend: Send + Sync {
+    /// Runs nmcli with `args`; returns stdout, or an error carrying stderr.
+    fn nmcli(&self, args: &[&str]) -> Result<String>;
+
+    /// Visible networks, strongest first, at most 10. Networks with one of the
+    /// `saved` connections get its UUID; connections are matched on their
+    /// SSID, as the connection name can be anything.
+    fn wifi_list(&self, saved: &[SavedConnection], ssids: &SsidCache) -> Result<Vec<WifiNetwork>> {
+        let stdout = self.nmcli(&["-t", "-f", "SSID,SIGNAL,SECURITY", "dev", "wifi"])?;
+
+        ssids.retain(saved);
+        let mut known: HashMap<String, String> = HashMap::new();
+        for connection in saved.iter().filter(|connection| connection.is_wifi()) {
+            if let Some(ssid) = ssids.get(self, &connection.uuid) {
+                // saved connections are most recently used first; keep that one
+                known.entry(ssid).or_insert(connection.uuid.clone());
+            }
+        }
+
+        let mut networks = parse_wifi_list(&stdout);
+        for network in &mut networks {
+            network.uuid = known.get(&network.ssid).cloned();
+        }
+        Ok(networks)
+    }
+
+    /// Saved connection profiles, most recently used first, without loopback.
+    fn saved_connections(&self) -> Result<Vec<SavedConnection>> {
+        let stdout = self.nmcli(&[
+            "-t",
+            "-f",
+            "NAME,UUID,TYPE,TIMESTAMP,AUTOCONNECT,AUTOCONNECT-PRIORITY",
+            "connection",
+            "show",
+        ])?;
+        Ok(parse_saved_connections(&stdout))
+    }
+
+    /// SSID a saved wifi connection joins.
+    fn connection_ssid(&self, uuid: &str) -> Result<String> {
+        let stdout = self.nmcli(&[
+            "-g",
+            "802-11-wireless.ssid",
+            "connection",
+            "show",
+            "uuid",
+            uuid,
+        ])?;
+        // a single field, so any `:` is part of the SSID whether escaped or not
+        Ok(split_terse(stdout.trim_end_matches('\n')).join(":"))
+    }
+
+    /// Deletes a saved connection with its credentials.
+    fn forget(&self, uuid: &str) -> Result<()> {
+        self.nmcli(&["connection", "delete", "uuid", uuid])?;
+        Ok(())
+    }
+
+    fn set_autoconnect(&self, uuid: &str, autoconnect: bool) -> Result<()> {
+        let value = if autoconnect { "yes" } else { "no" };
+        self.nmcli(&[
+            "connection",
+            "modify",
+            "uuid",
+            uuid,
+            "connection.autoconnect",
+            value,
+        ])?;
+        Ok(())
+    }
+
+    fn set_priority(&self, uuid: &str, priority: i32) -> Result<()> {
+        self.nmcli(&[
+            "connection",
+            "modify",
+            "uuid",
+            uuid,
+            "connection.autoconnect-priority",
+            &priority.to_string(),
+        ])?;
+        Ok(())
+    }
+
+    /// SSID of the active wifi, or an empty string.
+    fn connected_ssid(&self) -> Result<String> {
+        let stdout = self.nmcli(&["-t", "-f", "active,ssid", "dev", "wifi"])?;
+
+        Ok(stdout
+            .lines()
+            .map(split_terse)
+            .find(|fields| fields.len() == 2 && fields[0] == "yes")
+            .map(|mut fields| fields.swap_remove(1))
+            .unwrap_or_default())
+    }
+
+    fn connection_info(&self) -> Result<Vec<DeviceInfo>> {
+        let stdout = self.nmcli(&["device", "show"])?;
+        Ok(parse_device_show(&stdout))
+    }
+
+    fn connect(&self, ssid: &str, password: &str) -> Result<()> {
+        self.nmcli(&["device", "wifi", "connect", ssid, "password", password])?;
+        Ok(())
+    }
+
+    /// Connects to an open network.
+    fn connect_open(&self, ssid: &str) -> Result<()> {
+        self.nmcli(&["device", "wifi", "connect", ssid])?;
+        Ok(())
+    }
+
+    /// Activates a saved connection, using the credentials stored with it.
+    fn connect_saved(&self, uuid: &str) -> Result<()> {
+        self.nmcli(&["connection", "up", "uuid", uuid])?;
+        Ok(())
+    }
+
+    /// Connects to a network that does not broadcast its SSID.
+    fn connect_hidden(&self, ssid: &str, security: WifiSecurity, password: &str) -> Result<()> {
+        let mut args = vec!["device", "wifi", "connect", ssid];
+        match security {
+            WifiSecurity::Wpa => args.extend(["password", password]),
+            // an ASCII or hex key rather than a passphrase, like most WEP networks use
+            WifiSecurity::Wep => args.extend(["password", password, "wep-key-type", "key"]),
+            WifiSecurity::Open => {}
+        }
+        args.extend(["hidden", "yes"]);
+
+        self.nmcli(&args)?;
+        Ok(())
+    }
+
+    /// Disconnects the wifi device that is connected.
+    fn disconnect_wifi(&self) -> Result<()> {
+        let stdout = self.nmcli(&["-t", "-f", "DEVICE,TYPE,STATE", "device"])?;
+        let device = stdout
+            .lines()
+            .map(split_terse)
+            .find(|fields| fields.len() == 3 && fields[1] == "wifi" && fields[2] == "connected")
+            .map(|mut fields| fields.swap_remove(0))
+            .ok_or_else(|| eyre!("Not connected to a wifi network"))?;
+
+        self.nmcli(&["device", "disconnect", &device])?;
+        Ok(())
+    }
+
+    /// Whether the wifi radio is on.
+    fn wifi_radio(&self) -> Result<bool> {
+        let stdout = self.nmcli(&["radio", "wifi"])?;
+        Ok(stdout.trim() == "enabled")
+    }
+
+    fn set_wifi_radio(&self, on: bool) -> Result<()> {
+        self.nmcli(&["radio", "wifi", if on { "on" } else { "off" }])?;
+        Ok(())
+    }
+
+    /// Asks the wifi device to scan again; returns once the scan has finished.
+    fn rescan(&self) -> Result<()> {
+        self.nmcli(&["dev", "wifi", "rescan"])?;
+        Ok(())
+    }
+}
+
+/// Picks the fake backend when `AEX_NMCLI_FIXTURES` is set, nmcli otherwise.
+pub fn from_env() -> Arc<dyn NetworkBackend> {
+    match env::var("AEX_NMCLI_FIXTURES") {
+        Ok(path) => match FakeNmcliBackend::load(Path::new(&path)) {
+            Ok(backend) => Arc::new(backend),
+            Err(e) => Arc::new(FakeNmcliBackend::failing(&e.to_string())),
+        },
+        Err(_) => Arc::new(NmcliBackend),
+    }
+}
+
+// ====== nmcli ======
+
+pub struct NmcliBackend;
+
+impl NetworkBackend for NmcliBackend {
+    fn nmcli(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("nmcli")
+            .args(args)
+            .output()
+            .map_err(|e| eyre!("Failed to execute nmcli: {}", e))?;
+
+        if !output.status.success() {
+            return Err(eyre!(
+                "{}",
+                String::from_utf8_lossy(&output.stderr).trim().to_string()
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+// ====== Parsing ======
+
+/// Splits a line of `nmcli -t` output into fields, undoing the `\:` and `\\`
+/// escapes nmcli uses inside values.
+pub fn split_terse(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    fields.last_mut().unwrap().push(escaped);
+                }
+            }
+            ':' => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(ch),
+        }
+    }
+
+    fields
+}
+
+/// Parses `nmcli -t -f SSID,SIGNAL,SECURITY dev wifi`, keeping one network per signal level.
+pub fn parse_wifi_list(stdout: &str) -> Vec<WifiNetwork> {
+    let mut networks: Vec<WifiNetwork> = stdout
+        .lines()
+        .filter_map(|line| {
+            let fields = split_terse(line);
+            let ssid = fields.first()?.trim();
+            let signal = fields.get(1)?.trim().parse::<u8>().ok()?;
+            if ssid.is_empty() {
+                return None;
+            }
+
+            Some(WifiNetwork {
+                ssid: ssid.to_string(),
+                signal,
+                security: fields
+                    .get(2)
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_default(),
+                uuid: None,
+            })
+        })
+        .collect();
+
+    networks.sort_by_key(|n| std::cmp::Reverse(n.signal));
+    let mut seen_signals = HashSet::new();
+    networks
+        .into_iter()
+        .filter(|network| seen_signals.insert(network.signal))
+        .take(10)
+        .collect()
+}
+
+/// Parses `nmcli -t -f NAME,UUID,TYPE,TIMESTAMP,AUTOCONNECT,AUTOCONNECT-PRIORITY connection show`.
+pub fn parse_saved_connections(stdout: &str) -> Vec<SavedConnection> {
+    let mut connections: Vec<SavedConnection> = stdout
+        .lines()
+        .filter_map(|line| {
+            let fields = split_terse(line);
+            let [name, uuid, conn_type, timestamp, autoconnect, priority] = fields.as_slice()
+            else {
+                return None;
+            };
+            if conn_type == "loopback" {
+                return None;
+            }
+
+            Some(SavedConnection {
+                name: name.clone(),
+                uuid: uuid.clone(),
+                conn_type: conn_type.clone(),
+                last_used: timestamp
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|secs| *secs > 0)
+                    .and_then(|secs| Local.timestamp_opt(secs, 0).single()),
+                autoconnect: autoconnect == "yes",
+                priority: priority.parse().unwrap_or(0),
+            })
+        })
+        .collect();
+
+    connections.sort_by_key(|c| std::cmp::Reverse(c.last_used));
+    connections
+}
+
+/// Parses `nmcli device show`, keeping wifi and ethernet devices.
+pub fn parse_device_show(stdout: &str) -> Vec<DeviceInfo> {
+    let mut blocks = Vec::new();
+    let mut current_block = Vec::new();
+
+    for line in stdout.lines() {
+        if line.starts_with("GENERAL.DEVICE") && !current_block.is_empty() {
+            blocks.push(current_block.clone());
+            current_block.clear();
+        }
+        current_block.push(line);
+    }
+    if !current_block.is_empty() {
+        blocks.push(current_block);
+    }
+
+    let mut devices = Vec::new();
+
+    for block in blocks {
+        let mut device_type = "";
+        let mut device_name = "";
+
+        for line in &block {
+            if line.starts_with("GENERAL.TYPE:") {
+                device_type = value_of(line);
+            }
+            if line.starts_with("GENERAL.DEVICE:") {
+                device_name = value_of(line);
+            }
+        }
+
+        if device_type != "wifi" && device_type != "ethernet" {
+            continue;
+        }
+
+        let mut fields = Vec::new();
+        for (nmcli_field, label) in &DEVICE_FIELDS {
+            for line in &block {
+                if line.starts_with(nmcli_field) && line.contains(':') {
+                    fields.push((*label, value_of(line).to_string()));
+                }
+            }
+        }
+
+        devices.push(DeviceInfo {
+            name: device_name.to_string(),
+            device_type: device_type.to_string(),
+            fields,
+        });
+    }
+
+    devices
+}
+
+/// Value of a `FIELD: value` line.
+fn value_of(line: &str) -> &str {
+    line.split_once(':').map(|x| x.1).unwrap_or("").trim()
+}
+
+// ====== Fake ======
+
+/// One recorded nmcli call from a fixture file.
+struct Fixture {
+    args: Vec<String>,
+    stdout: String,
+    stderr: Option<String>,
+}
+
+impl Fixture {
+    fn matches(&self, args: &[&str]) -> bool {
+        self.args.len() == args.len()
+            && self
+                .args
+                .iter()
+                .zip(args)
+                .all(|(pattern, arg)| pattern == "*" || pattern == arg)
+    }
+
+    fn wildcards(&self) -> usize {
+        self.args.iter().filter(|pattern| *pattern == "*").count()
+    }
+}
+
+/// Replays nmcli output captured in a fixture file.
+///
+/// Each block starts with `$ nmcli <args>` (`*` matches any single argument)
+/// and is followed by its stdout. Lines starting with `! ` are stderr and make
+/// the call fail. When several patterns match a call, the one with the fewest
+/// wildcards is used. Blocks recorded with identical args are replayed in
+/// order and the last one repeats. Calls without a fixture fail.
+pub struct FakeNmcliBackend {
+    fixtures: Vec<Fixture>,
+    /// times each fixture command has been replayed, keyed by its args
+    replayed: Mutex<HashMap<Vec<String>, usize>>,
+    /// set when the fixture file could not be read; every call fails with it
+    error: Option<String>,
+}
+
+impl FakeNmcliBackend {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| eyre!("Failed to read {}: {}", path.display(), e))?;
+        Ok(Self::parse(&text))
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let mut fixtures: Vec<Fixture> = Vec::new();
+
+        for line in text.lines() {
+            if let Some(command) = line.strip_prefix("$ nmcli") {
+                fixtures.push(Fixture {
+                    args: command.split_whitespace().map(str::to_string).collect(),
+                    stdout: String::new(),
+                    stderr: None,
+                });
+            } else if let Some(fixture) = fixtures.last_mut() {
+                if let Some(error) = line.strip_prefix("! ") {
+                    let stderr = fixture.stderr.get_or_insert_with(String::new);
+                    stderr.push_str(error);
+                    stderr.push('\n');
+                } else {
+                    fixture.stdout.push_str(line);
+                    fixture.stdout.push('\n');
+                }
+            }
+        }
+
+        Self {
+            fixtures,
+            replayed: Mutex::new(HashMap::new()),
+            error: None,
+        }
+    }
+
+    /// A backend whose every call fails with `reason`.
+    fn failing(reason: &str) -> Self {
+        Self {
+            error: Some(reason.to_string()),
+            ..Self::parse("")
+        }
+    }
+}
+
+impl NetworkBackend for FakeNmcliBackend {
+    fn nmcli(&self, args: &[&str]) -> Result<String> {
+        if let Some(error) = &self.error {
+            return Err(eyre!("{}", error.clone()));
+        }
+
+        // the pattern with the fewest wildcards wins; on a tie the first one recorded
+        let Some(best) = self
+            .fixtures
+            .iter()
+            .filter(|f| f.matches(args))
+            .min_by_key(|f| f.wildcards())
+        else {
+            return Err(eyre!("No fixture for nmcli {}", args.join(" ")));
+        };
+
+        let sequence: Vec<&Fixture> = self
+            .fixtures
+            .iter()
+            .filter(|f| f.args == best.args)
+            .collect();
+
+        let mut replayed = self.replayed.lock().unwrap();
+        let count = replayed.entry(best.args.clone()).or_insert(0);
+        let fixture = sequence[(*count).min(sequence.len() - 1)];
+        *count += 1;
+
+        match &fixture.stderr {
+            Some(stderr) => Err(eyre!("{}", stderr.trim().to_string())),
+            None => Ok(fixture.stdout.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: &str = include_str!("../../fixtures/nmcli.txt");
+
+    #[test]
+    fn splits_terse_fields_and_unescapes() {
+        assert_eq!(split_terse("HomeNet:82:WPA2"), ["HomeNet", "82", "WPA2"]);
+        assert_eq!(
+            split_terse(r"Printer\:Direct:18:"),
+            ["Printer:Direct", "18", ""]
+        );
+        assert_eq!(split_terse(r"back\\slash:1"), [r"back\slash", "1"]);
+        assert_eq!(split_terse(""), [""]);
+    }
+
+    #[test]
+    fn parses_wifi_list_strongest_first() {
+        let networks = parse_wifi_list(
+            "HomeNet:82:WPA2\n\
+             Cafe Guest:64:\n\
+             :70:WPA2\n\
+             Neighbours 5G:41:WPA1 WPA2\n\
+             HomeNet:39:WPA2\n\
+             Printer\\:Direct:18:WPA2\n\
+             Other:41:WPA2\n\
+             garbage\n",
+        );
+
+        let ssids: Vec<&str> = networks.iter().map(|n| n.ssid.as_str()).collect();
+        // hidden networks are skipped and only the first network per signal level is kept
+        assert_eq!(
+            ssids,
+            [
+                "HomeNet",
+                "Cafe Guest",
+                "Neighbours 5G",
+                "HomeNet",
+                "Printer:Direct"
+            ]
+        );
+        assert!(networks[1].is_open());
+        assert_eq!(networks[2].security, "WPA1 WPA2");
+        assert!(networks.iter().all(|n| !n.is_known()));
+    }
+
+    #[test]
+    fn wifi_list_is_capped_at_ten() {
+        let stdout: String = (0..20).map(|i| format!("Net {}:{}:WPA2\n", i, i)).collect();
+        assert_eq!(parse_wifi_list(&stdout).len(), 10);
+    }
+
+    #[test]
+    fn parses_device_show_keeping_wifi_and_ethernet() {
+        let devices = parse_device_show(
+            "GENERAL.DEVICE:                         wlan0\n\
+             GENERAL.TYPE:                           wifi\n\
+             GENERAL.HWADDR:                         3C:A9:F4:12:34:56\n\
+             GENERAL.CONNECTION:                     HomeNet\n\
+             IP4.ADDRESS[1]:                         192.168.1.23/24\n\
+             IP4.GATEWAY:                            192.168.1.1\n\
+             IP4.DNS[1]:                             192.168.1.1\n\
+             IP4.DNS[2]:                             1.1.1.1\n\
+             GENERAL.DEVICE:                         enp3s0\n\
+             GENERAL.TYPE:                           ethernet\n\
+             GENERAL.CONNECTION:                     --\n\
+             GENERAL.DEVICE:                         lo\n\
+             GENERAL.TYPE:                           loopback\n",
+        );
+
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].name, "wlan0");
+        assert_eq!(devices[0].device_type, "wifi");
+        assert_eq!(
+            devices[0].fields,
+            [
+                ("Device", "wlan0".to_string()),
+                ("Type", "wifi".to_string()),
+                ("Name", "HomeNet".to_string()),
+                ("IPv4", "192.168.1.23/24".to_string()),
+                ("IPv4 Gateway", "192.168.1.1".to_string()),
+                ("DNS", "192.168.1.1".to_string()),
+                ("DNS", "1.1.1.1".to_string()),
+            ]
+        );
+        assert_eq!(devices[1].name, "enp3s0");
+    }
+
+    #[test]
+    fn replays_the_bundled_fixtures() {
+        let backend = FakeNmcliBackend::parse(FIXTURES);
+
+        let saved = backend.saved_connections().unwrap();
+        let networks = backend.wifi_list(&saved, &SsidCache::default()).unwrap();
+        let known: Vec<(&str, &str)> = networks
+            .iter()
+            .filter_map(|n| Some((n.ssid.as_str(), n.uuid.as_deref()?)))
+            .collect();
+        // the "Neighbours" connection joins the SSID "Neighbours 5G"
+        assert_eq!(
+            known,
+            [
+                ("HomeNet", "0f6a3c2e-7d41-4b8a-9c1e-2a5b6c7d8e9f"),
+                ("Neighbours 5G", "f6a7b8c9-d0e1-4f2a-8b3c-4d5e6f7a8b9c"),
+                ("HomeNet", "0f6a3c2e-7d41-4b8a-9c1e-2a5b6c7d8e9f"),
+            ]
+        );
+        assert!(
+            backend
+                .connect_saved("0f6a3c2e-7d41-4b8a-9c1e-2a5b6c7d8e9f")
+                .is_ok()
+        );
+        assert_eq!(backend.connected_ssid().unwrap(), "HomeNet");
+        assert!(!saved.iter().any(|c| c.name == "lo"));
+        assert!(backend.nmcli(&["no", "such", "command"]).is_err());
+
+        // the radio reads as on once, then as off
+        assert!(backend.wifi_radio().unwrap());
+        assert!(!backend.wifi_radio().unwrap());
+        assert!(!backend.wifi_radio().unwrap());
+    }
+
+    #[test]
+    fn reads_the_ssid_of_a_saved_connection() {
+        let backend = FakeNmcliBackend::parse(
+            "$ nmcli -g 802-11-wireless.ssid connection show uuid 1\n\
+             Printer\\:Direct\n",
+        );
+        assert_eq!(backend.connection_ssid("1").unwrap(), "Printer:Direct");
+    }
+
+    #[test]
+    fn ssids_are_asked_for_once_per_connection() {
+        let backend = FakeNmcliBackend::parse(FIXTURES);
+        let saved = backend.saved_connections().unwrap();
+        let ssids = SsidCache::default();
+
+        for _ in 0..3 {
+            backend.wifi_list(&saved, &ssids).unwrap();
+        }
+
+        let replayed = backend.replayed.lock().unwrap();
+        let ssid_queries: Vec<usize> = replayed
+            .iter()
+            .filter(|(args, _)| args.contains(&"802-11-wireless.ssid".to_string()))
+            .map(|(_, count)| *count)
+            .collect();
+        assert_eq!(ssid_queries, [1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn forgotten_connections_leave_the_ssid_cache() {
+        let backend = FakeNmcliBackend::parse(FIXTURES);
+        let saved = backend.saved_connections().unwrap();
+        let ssids = SsidCache::default();
+        backend.wifi_list(&saved, &ssids).unwrap();
+
+        let networks = backend.wifi_list(&saved[..1], &ssids).unwrap();
+        assert_eq!(ssids.0.lock().unwrap().len(), 1);
+        assert!(
+            networks
+                .iter()
+                .all(|n| n.ssid == "HomeNet" || !n.is_known())
+        );
+    }
+
+    #[test]
+    fn most_specific_pattern_wins_regardless_of_order() {
+        let backend = FakeNmcliBackend::parse(
+            "$ nmcli device wifi connect * password *\n\
+             ! Error: Secrets were required, but not provided.\n\
+             $ nmcli device wifi connect HomeNet password *\n\
+             ok\n",
+        );
+
+        let connect = |ssid| backend.nmcli(&["device", "wifi", "connect", ssid, "password", "x"]);
+        assert!(connect("Cafe").is_err());
+        assert_eq!(connect("HomeNet").unwrap(), "ok\n");
+        assert!(connect("Cafe").is_err());
+        assert_eq!(connect("HomeNet").unwrap(), "ok\n");
+    }
+
+    #[test]
+    fn replays_identical_blocks_in_order_and_repeats_the_last() {
+        let backend = FakeNmcliBackend::parse(
+            "$ nmcli radio wifi\n\
+             enabled\n\
+             $ nmcli radio wifi *\n\
+             $ nmcli radio wifi\n\
+             disabled\n",
+        );
+
+        assert_eq!(backend.nmcli(&["radio", "wifi"]).unwrap(), "enabled\n");
+        // a different pattern neither advances nor joins the sequence
+        assert_eq!(backend.nmcli(&["radio", "wifi", "off"]).unwrap(), "");
+        assert_eq!(backend.nmcli(&["radio", "wifi"]).unwrap(), "disabled\n");
+        assert_eq!(backend.nmcli(&["radio", "wifi"]).unwrap(), "disabled\n");
+    }
+
+    /// Answers `connection show` for one connection only, counting the calls.
+    struct SsidLookups(Mutex<usize>);
+
+    impl NetworkBackend for SsidLookups {
+        fn nmcli(&self, args: &[&str]) -> Result<String> {
+            *self.0.lock().unwrap() += 1;
+            match args.last() {
+                Some(&"home") => Ok("HomeNet\n".to_string()),
+                _ => Err(eyre!("Error: no such connection profile.")),
+            }
+        }
+    }
+
+    #[test]
+    fn ssid_cache_keeps_failed_lookups_until_the_connection_is_gone() {
+        let backend = SsidLookups(Mutex::new(0));
+        let cache = SsidCache::default();
+        let saved = |uuids: &[&str]| -> Vec<SavedConnection> {
+            uuids
+                .iter()
+                .map(|uuid| SavedConnection {
+                    name: uuid.to_string(),
+                    uuid: uuid.to_string(),
+                    conn_type: "802-11-wireless".to_string(),
+                    last_used: None,
+                    autoconnect: true,
+                    priority: 0,
+                })
+                .collect()
+        };
+
+        for _ in 0..2 {
+            assert_eq!(cache.get(&backend, "home").as_deref(), Some("HomeNet"));
+            assert_eq!(cache.get(&backend, "broken"), None);
+        }
+        assert_eq!(*backend.0.lock().unwrap(), 2);
+
+        cache.retain(&saved(&["home"]));
+        assert_eq!(cache.get(&backend, "broken"), None);
+        assert_eq!(*backend.0.lock().unwrap(), 3);
+    }
+}
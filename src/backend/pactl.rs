@@ -0,0 +1,658 @@
+//! `AudioBackend` implementation that runs `pactl`.
+//!
+//! Lists are read from `pactl --format=json` when the installed pactl supports
+//! it, and from its text output otherwise. Every call runs with `LC_ALL=C`, so
+//! the text fallback always sees the English field names.
+
+use color_eyre::{Result, eyre::eyre};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader},
+    process::{ChildStdout, Command, Stdio},
+    sync::OnceLock,
+};
+
+use crate::backend::audio::{AudioBackend, AudioDevice, ChannelVolume, SinkInput};
+
+pub struct PactlBackend {
+    /// whether `pactl --format=json` works, checked on first use
+    json: OnceLock<bool>,
+}
+
+impl PactlBackend {
+    pub fn new() -> Self {
+        Self {
+            json: OnceLock::new(),
+        }
+    }
+
+    fn command() -> Command {
+        let mut command = Command::new("pactl");
+        command.env("LC_ALL", "C");
+        command
+    }
+
+    fn run(args: &[&str]) -> Result<String> {
+        let output = Self::command()
+            .args(args)
+            .output()
+            .map_err(|e| eyre!("Failed to run pactl: {}", e))?;
+
+        if !output.status.success() {
+            return Err(eyre!(
+                "pactl {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn supports_json(&self) -> bool {
+        *self.json.get_or_init(|| {
+            Self::run(&["--format=json", "info"])
+                .is_ok_and(|stdout| serde_json::from_str::<Value>(&stdout).is_ok())
+        })
+    }
+
+    /// `pactl list <kind>` as objects.
+    fn list(&self, kind: &str) -> Result<Vec<PactlObject>> {
+        if self.supports_json() {
+            let stdout = Self::run(&["--format=json", "list", kind])?;
+            parse_json_list(&stdout)
+        } else {
+            let stdout = Self::run(&["list", kind])?;
+            Ok(parse_text_list(&stdout))
+        }
+    }
+
+    /// A default device name from `pactl info`.
+    fn default_device(&self, json_key: &str, text_key: &str) -> Result<String> {
+        if self.supports_json() {
+            let info: Value = serde_json::from_str(&Self::run(&["--format=json", "info"])?)?;
+            return Ok(info.get(json_key).map(value_to_string).unwrap_or_default());
+        }
+
+        let stdout = Self::run(&["info"])?;
+        Ok(stdout
+            .lines()
+            .find_map(|line| line.strip_prefix(text_key))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default())
+    }
+
+    fn set(command: &str, target: &str, value: &str) -> Result<()> {
+        Self::run(&[command, target, value])?;
+        Ok(())
+    }
+
+    /// `pactl set-*-volume <target> 60% 40%`, one value per channel.
+    fn set_volume(command: &str, target: &str, volumes: &[u8]) -> Result<()> {
+        let volumes: Vec<String> = volumes.iter().map(|v| format!("{}%", v)).collect();
+        let mut args = vec![command, target];
+        args.extend(volumes.iter().map(String::as_str));
+        Self::run(&args)?;
+        Ok(())
+    }
+
+    /// Calls `on_change` for every mixer event in `pactl subscribe` output
+    /// until it returns `false` or the output ends.
+    fn read_events(stdout: ChildStdout, on_change: &mut dyn FnMut() -> bool) -> Result<()> {
+        for line in BufReader::new(stdout).lines() {
+            if is_mixer_event(&line?) && !on_change() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn mute_arg(muted: bool) -> &'static str {
+    if muted { "1" } else { "0" }
+}
+
+impl AudioBackend for PactlBackend {
+    fn sink_inputs(&self) -> Result<Vec<SinkInput>> {
+        Ok(self
+            .list("sink-inputs")?
+            .into_iter()
+            .map(PactlObject::into_sink_input)
+            .collect())
+    }
+
+    fn set_sink_input_volume(&self, id: &str, volumes: &[u8]) -> Result<()> {
+        Self::set_volume("set-sink-input-volume", id, volumes)
+    }
+
+    fn set_sink_input_mute(&self, id: &str, muted: bool) -> Result<()> {
+        Self::set("set-sink-input-mute", id, mute_arg(muted))
+    }
+
+    fn move_sink_input(&self, id: &str, sink: &str) -> Result<()> {
+        Self::set("move-sink-input", id, sink)
+    }
+
+    fn sinks(&self) -> Result<Vec<AudioDevice>> {
+        Ok(self
+            .list("sinks")?
+            .into_iter()
+            .map(PactlObject::into_device)
+            .collect())
+    }
+
+    fn default_sink(&self) -> Result<String> {
+        self.default_device("default_sink_name", "Default Sink:")
+    }
+
+    fn set_default_sink(&self, name: &str) -> Result<()> {
+        Self::run(&["set-default-sink", name])?;
+        Ok(())
+    }
+
+    fn set_sink_volume(&self, name: &str, volumes: &[u8]) -> Result<()> {
+        Self::set_volume("set-sink-volume", name, volumes)
+    }
+
+    fn set_sink_mute(&self, name: &str, muted: bool) -> Result<()> {
+        Self::set("set-sink-mute", name, mute_arg(muted))
+    }
+
+    fn sources(&self) -> Result<Vec<AudioDevice>> {
+        Ok(self
+            .list("sources")?
+            .into_iter()
+            .filter(|source| !source.is_monitor())
+            .map(PactlObject::into_device)
+            .collect())
+    }
+
+    fn default_source(&self) -> Result<String> {
+        self.default_device("default_source_name", "Default Source:")
+    }
+
+    fn set_default_source(&self, name: &str) -> Result<()> {
+        Self::run(&["set-default-source", name])?;
+        Ok(())
+    }
+
+    fn set_source_volume(&self, name: &str, volumes: &[u8]) -> Result<()> {
+        Self::set_volume("set-source-volume", name, volumes)
+    }
+
+    fn set_source_mute(&self, name: &str, muted: bool) -> Result<()> {
+        Self::set("set-source-mute", name, mute_arg(muted))
+    }
+
+    fn subscribe(&self, on_change: &mut dyn FnMut() -> bool) -> Result<()> {
+        let mut child = Self::command()
+            .arg("subscribe")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| eyre!("Failed to run pactl subscribe: {}", e))?;
+
+        let events = match child.stdout.take() {
+            Some(stdout) => Self::read_events(stdout, on_change),
+            None => Err(eyre!("pactl subscribe has no output")),
+        };
+
+        // also on errors, so no `pactl subscribe` is left running
+        let _ = child.kill();
+        let _ = child.wait();
+        events
+    }
+}
+
+/// Whether a `pactl subscribe` line (`Event 'change' on sink-input #42`) is
+/// about something the mixer shows; `server` covers default device changes.
+pub fn is_mixer_event(line: &str) -> bool {
+    let Some((_, object)) = line.split_once(" on ") else {
+        return false;
+    };
+
+    matches!(
+        object.split_whitespace().next(),
+        Some("sink-input" | "sink" | "source" | "server")
+    )
+}
+
+// ====== Parsing ======
+
+/// One sink, source or sink-input from `pactl list`, in either output format.
+#[derive(Debug, Default)]
+pub struct PactlObject {
+    pub index: String,
+    pub name: String,
+    pub description: String,
+    /// sink index of a sink-input
+    pub sink: String,
+    /// set on sources that record the output of a sink
+    pub monitor_of_sink: Option<String>,
+    pub muted: bool,
+    pub channels: Vec<ChannelVolume>,
+    pub properties: HashMap<String, String>,
+}
+
+impl PactlObject {
+    fn property(&self, key: &str) -> Option<String> {
+        self.properties
+            .get(key)
+            .filter(|value| !value.is_empty())
+            .cloned()
+    }
+
+    fn is_monitor(&self) -> bool {
+        self.monitor_of_sink.is_some()
+            || self.name.ends_with(".monitor")
+            || self.property("device.class").as_deref() == Some("monitor")
+    }
+
+    fn into_device(self) -> AudioDevice {
+        let description = if self.description.is_empty() {
+            self.name.clone()
+        } else {
+            self.description
+        };
+
+        AudioDevice {
+            index: self.index,
+            name: self.name,
+            description,
+            channels: self.channels,
+            muted: self.muted,
+        }
+    }
+
+    /// Named after the application, falling back to the media name, the
+    /// binary and finally the stream index.
+    fn into_sink_input(self) -> SinkInput {
+        let media_name = self.property("media.name");
+        let binary = self.property("application.process.binary");
+        let name = self
+            .property("application.name")
+            .or_else(|| media_name.clone())
+            .or_else(|| binary.clone())
+            .unwrap_or_else(|| format!("Stream #{}", self.index));
+
+        SinkInput {
+            pid: self
+                .property("application.process.id")
+                .and_then(|pid| pid.parse().ok()),
+            id: self.index,
+            name,
+            media_name,
+            binary,
+            sink: self.sink,
+            channels: self.channels,
+            muted: self.muted,
+        }
+    }
+}
+
+/// Parses `pactl --format=json list <kind>`.
+pub fn parse_json_list(stdout: &str) -> Result<Vec<PactlObject>> {
+    let items: Vec<Value> = serde_json::from_str(stdout)?;
+
+    Ok(items
+        .iter()
+        .map(|item| {
+            let text = |key: &str| item.get(key).map(value_to_string).unwrap_or_default();
+
+            let channels = item
+                .get("volume")
+                .and_then(Value::as_object)
+                .map(|volume| {
+                    volume
+                        .iter()
+                        .map(|(channel, level)| ChannelVolume {
+                            channel: channel.clone(),
+                            percent: parse_percent(
+                                level
+                                    .get("value_percent")
+                                    .and_then(Value::as_str)
+                                    .unwrap_or(""),
+                            ),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            let properties = item
+                .get("properties")
+                .and_then(Value::as_object)
+                .map(|properties| {
+                    properties
+                        .iter()
+                        .map(|(key, value)| (key.clone(), value_to_string(value)))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            PactlObject {
+                index: text("index"),
+                name: text("name"),
+                description: text("description"),
+                sink: text("sink"),
+                monitor_of_sink: Some(text("monitor_of_sink"))
+                    .filter(|sink| !sink.is_empty() && sink != "n/a"),
+                muted: item.get("mute").and_then(Value::as_bool).unwrap_or(false),
+                channels,
+                properties,
+            }
+        })
+        .collect())
+}
+
+/// Parses `LC_ALL=C pactl list <kind>`. Objects start with an unindented
+/// `Sink #0` style header, fields are `Key: value` lines and properties are
+/// `key = "value"` lines under `Properties:`.
+pub fn parse_text_list(stdout: &str) -> Vec<PactlObject> {
+    let mut objects: Vec<PactlObject> = Vec::new();
+    let mut in_properties = false;
+
+    for line in stdout.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            if let Some((_, index)) = line.split_once('#') {
+                objects.push(PactlObject {
+                    index: index.trim().to_string(),
+                    ..Default::default()
+                });
+            }
+            in_properties = false;
+            continue;
+        }
+
+        let Some(object) = objects.last_mut() else {
+            continue;
+        };
+        let trimmed = line.trim();
+
+        if in_properties {
+            if let Some((key, value)) = trimmed.split_once(" = ") {
+                object
+                    .properties
+                    .insert(key.to_string(), value.trim_matches('"').to_string());
+                continue;
+            }
+            in_properties = false;
+        }
+
+        if trimmed == "Properties:" {
+            in_properties = true;
+            continue;
+        }
+
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        match key {
+            "Name" => object.name = value.to_string(),
+            "Description" => object.description = value.to_string(),
+            "Sink" => object.sink = value.to_string(),
+            "Mute" => object.muted = value == "yes",
+            "Volume" => object.channels = parse_text_volume(value),
+            "Monitor of Sink" if value != "n/a" => object.monitor_of_sink = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    objects
+}
+
+/// Parses `front-left: 65536 / 100% / 0.00 dB,   front-right: ...`.
+fn parse_text_volume(value: &str) -> Vec<ChannelVolume> {
+    value
+        .split(',')
+        .filter_map(|part| {
+            let (channel, levels) = part.split_once(':')?;
+            let percent = levels.split('/').nth(1)?;
+            Some(ChannelVolume {
+                channel: channel.trim().to_string(),
+                percent: parse_percent(percent),
+            })
+        })
+        .collect()
+}
+
+/// `"100%"` to 100; values above 255% are capped.
+fn parse_percent(text: &str) -> u8 {
+    text.trim()
+        .trim_end_matches('%')
+        .parse::<u32>()
+        .map(|percent| percent.min(u8::MAX as u32) as u8)
+        .unwrap_or(0)
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `LC_ALL=C pactl list sink-inputs`: a named stereo stream, a stream with
+    /// only a media name, a mono stream without any name and a muted stream.
+    const TEXT_SINK_INPUTS: &str = "\
+Sink Input #42
+	Driver: protocol-native.c
+	Owner Module: 10
+	Client: 55
+	Sink: 0
+	Sample Specification: float32le 2ch 48000Hz
+	Channel Map: front-left,front-right
+	Format: pcm, format.sample_format = \"\\\"float32le\\\"\"  format.rate = \"48000\"
+	Corked: no
+	Mute: no
+	Volume: front-left: 39322 /  60% / -13.31 dB,   front-right: 26214 /  40% / -23.88 dB
+	        balance -0.33
+	Buffer Latency: 0 usec
+	Sink Latency: 0 usec
+	Resample method: n/a
+	Properties:
+		application.name = \"Firefox\"
+		application.process.id = \"4242\"
+		application.process.binary = \"firefox\"
+		media.name = \"AudioStream\"
+
+Sink Input #43
+	Driver: protocol-native.c
+	Sink: 1
+	Mute: no
+	Volume: front-left: 65536 / 100% / 0.00 dB,   front-right: 65536 / 100% / 0.00 dB
+	        balance 0.00
+	Properties:
+		media.name = \"Notification\"
+
+Sink Input #44
+	Driver: protocol-native.c
+	Sink: 0
+	Channel Map: mono
+	Mute: no
+	Volume: mono: 49807 /  76% / -7.15 dB
+	        balance 0.00
+	Properties:
+		application.process.id = \"99\"
+
+Sink Input #45
+	Sink: 0
+	Mute: yes
+	Volume: front-left: 98304 / 150% / 10.57 dB,   front-right: 98304 / 150% / 10.57 dB
+	Properties:
+		application.name = \"\"
+		application.process.binary = \"mpv\"
+";
+
+    /// The same streams from `pactl --format=json list sink-inputs`.
+    const JSON_SINK_INPUTS: &str = r#"[
+{"index":42,"driver":"protocol-native.c","owner_module":"10","client":"55","sink":0,"sample_specification":"float32le 2ch 48000Hz","channel_map":"front-left,front-right","corked":false,"mute":false,"volume":{"front-left":{"value":39322,"value_percent":"60%","db":"-13.31 dB"},"front-right":{"value":26214,"value_percent":"40%","db":"-23.88 dB"}},"balance":-0.33,"properties":{"application.name":"Firefox","application.process.id":"4242","application.process.binary":"firefox","media.name":"AudioStream"}},
+{"index":43,"driver":"protocol-native.c","sink":1,"mute":false,"volume":{"front-left":{"value":65536,"value_percent":"100%","db":"0.00 dB"},"front-right":{"value":65536,"value_percent":"100%","db":"0.00 dB"}},"properties":{"media.name":"Notification"}},
+{"index":44,"driver":"protocol-native.c","sink":0,"channel_map":"mono","mute":false,"volume":{"mono":{"value":49807,"value_percent":"76%","db":"-7.15 dB"}},"properties":{"application.process.id":"99"}},
+{"index":45,"sink":0,"mute":true,"volume":{"front-left":{"value":98304,"value_percent":"150%","db":"10.57 dB"},"front-right":{"value":98304,"value_percent":"150%","db":"10.57 dB"}},"properties":{"application.name":"","application.process.binary":"mpv"}}
+]"#;
+
+    /// `LC_ALL=C pactl list sources`: the monitor of a sink and a microphone.
+    const TEXT_SOURCES: &str = "\
+Source #1
+	State: SUSPENDED
+	Name: alsa_output.pci-0000_00_1f.3.analog-stereo.monitor
+	Description: Monitor of Built-in Audio Analog Stereo
+	Driver: module-alsa-card.c
+	Mute: no
+	Volume: front-left: 65536 / 100% / 0.00 dB,   front-right: 65536 / 100% / 0.00 dB
+	        balance 0.00
+	Base Volume: 65536 / 100% / 0.00 dB
+	Monitor of Sink: 0
+	Latency: 0 usec, configured 0 usec
+	Properties:
+		device.description = \"Monitor of Built-in Audio Analog Stereo\"
+		device.class = \"monitor\"
+
+Source #2
+	State: RUNNING
+	Name: alsa_input.pci-0000_00_1f.3.analog-stereo
+	Description: Built-in Audio Analog Stereo
+	Mute: yes
+	Volume: front-left: 52429 /  80% / -5.81 dB,   front-right: 52429 /  80% / -5.81 dB
+	        balance 0.00
+	Monitor of Sink: n/a
+	Properties:
+		device.class = \"sound\"
+";
+
+    const JSON_SOURCES: &str = r#"[
+{"index":1,"state":"SUSPENDED","name":"alsa_output.pci-0000_00_1f.3.analog-stereo.monitor","description":"Monitor of Built-in Audio Analog Stereo","mute":false,"volume":{"front-left":{"value":65536,"value_percent":"100%","db":"0.00 dB"},"front-right":{"value":65536,"value_percent":"100%","db":"0.00 dB"}},"monitor_of_sink":"0","properties":{"device.class":"monitor"}},
+{"index":2,"state":"RUNNING","name":"alsa_input.pci-0000_00_1f.3.analog-stereo","description":"Built-in Audio Analog Stereo","mute":true,"volume":{"front-left":{"value":52429,"value_percent":"80%","db":"-5.81 dB"},"front-right":{"value":52429,"value_percent":"80%","db":"-5.81 dB"}},"monitor_of_sink":"n/a","properties":{"device.class":"sound"}}
+]"#;
+
+    fn sink_inputs(objects: Vec<PactlObject>) -> Vec<SinkInput> {
+        objects
+            .into_iter()
+            .map(PactlObject::into_sink_input)
+            .collect()
+    }
+
+    fn assert_sink_inputs(inputs: &[SinkInput]) {
+        let names: Vec<&str> = inputs.iter().map(|input| input.name.as_str()).collect();
+        assert_eq!(names, ["Firefox", "Notification", "Stream #44", "mpv"]);
+
+        let firefox = &inputs[0];
+        assert_eq!(firefox.id, "42");
+        assert_eq!(firefox.sink, "0");
+        assert_eq!(firefox.pid, Some(4242));
+        assert_eq!(firefox.binary.as_deref(), Some("firefox"));
+        assert_eq!(firefox.media_name.as_deref(), Some("AudioStream"));
+        assert_eq!(
+            firefox.channels,
+            [
+                ChannelVolume {
+                    channel: "front-left".into(),
+                    percent: 60
+                },
+                ChannelVolume {
+                    channel: "front-right".into(),
+                    percent: 40
+                },
+            ]
+        );
+        assert!(!firefox.muted);
+
+        assert_eq!(inputs[1].sink, "1");
+        assert_eq!(inputs[1].binary, None);
+
+        let mono = &inputs[2];
+        assert_eq!(mono.pid, Some(99));
+        assert_eq!(mono.channels.len(), 1);
+        assert_eq!(mono.channels[0].channel, "mono");
+        assert_eq!(mono.channels[0].percent, 76);
+
+        // an empty application.name falls through to the binary
+        assert!(inputs[3].muted);
+        assert_eq!(inputs[3].channels[0].percent, 150);
+    }
+
+    #[test]
+    fn parses_text_sink_inputs() {
+        assert_sink_inputs(&sink_inputs(parse_text_list(TEXT_SINK_INPUTS)));
+    }
+
+    #[test]
+    fn parses_json_sink_inputs() {
+        assert_sink_inputs(&sink_inputs(parse_json_list(JSON_SINK_INPUTS).unwrap()));
+    }
+
+    #[test]
+    fn text_and_json_sources_agree_and_mark_monitors() {
+        for objects in [
+            parse_text_list(TEXT_SOURCES),
+            parse_json_list(JSON_SOURCES).unwrap(),
+        ] {
+            let monitors: Vec<bool> = objects.iter().map(PactlObject::is_monitor).collect();
+            assert_eq!(monitors, [true, false]);
+
+            let devices: Vec<AudioDevice> =
+                objects.into_iter().map(PactlObject::into_device).collect();
+            assert_eq!(devices[1].index, "2");
+            assert_eq!(devices[1].name, "alsa_input.pci-0000_00_1f.3.analog-stereo");
+            assert_eq!(devices[1].description, "Built-in Audio Analog Stereo");
+            assert!(devices[1].muted);
+            assert_eq!(devices[1].channels[0].percent, 80);
+        }
+    }
+
+    #[test]
+    fn monitor_is_detected_by_name_alone() {
+        let object = PactlObject {
+            name: "bluez_output.00_11_22.monitor".into(),
+            ..Default::default()
+        };
+        assert!(object.is_monitor());
+    }
+
+    #[test]
+    fn device_without_description_uses_its_name() {
+        let device = parse_text_list("Sink #3\n\tName: null-sink\n")
+            .remove(0)
+            .into_device();
+        assert_eq!(device.description, "null-sink");
+    }
+
+    #[test]
+    fn parses_text_volumes() {
+        let channels = parse_text_volume(
+            "front-left: 65536 / 100% / 0.00 dB,   front-right: 32768 /  50% / -18.06 dB",
+        );
+        let percents: Vec<u8> = channels.iter().map(|c| c.percent).collect();
+        assert_eq!(percents, [100, 50]);
+
+        assert_eq!(
+            parse_text_volume("mono: 196608 / 300% / 28.63 dB")[0].percent,
+            255
+        );
+        assert!(parse_text_volume("garbage").is_empty());
+    }
+
+    #[test]
+    fn invalid_json_is_an_error() {
+        assert!(parse_json_list("Sink #0").is_err());
+    }
+
+    #[test]
+    fn recognizes_mixer_events() {
+        assert!(is_mixer_event("Event 'change' on sink-input #42"));
+        assert!(is_mixer_event("Event 'new' on sink #1"));
+        assert!(is_mixer_event("Event 'remove' on source #3"));
+        assert!(is_mixer_event("Event 'change' on server #-1"));
+        assert!(!is_mixer_event("Event 'change' on client #7"));
+        assert!(!is_mixer_event("Event 'new' on source-output #9"));
+        assert!(!is_mixer_event("garbage"));
+    }
+}
@@ -0,0 +1,439 @@
+//! A private session bus with one MPRIS player on it, for testing
+//! `MprisBackend` against a real `dbus-daemon` and `busctl`.
+//!
+//! The player speaks just enough of the D-Bus wire protocol to answer
+//! `Properties.Get` and record the player methods it is called with.
+
+use std::{
+    env,
+    io::{BufRead, BufReader, Read, Write},
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex, mpsc},
+    thread,
+};
+
+pub const PLAYER_NAME: &str = "org.mpris.MediaPlayer2.test";
+
+const METHOD_CALL: u8 = 1;
+const METHOD_RETURN: u8 = 2;
+const ERROR: u8 = 3;
+
+/// Runs `dbus-daemon` until dropped; `calls` lists the player methods called,
+/// e.g. `Seek -5000000`.
+pub struct TestBus {
+    daemon: Child,
+    socket_dir: PathBuf,
+    pub address: String,
+    pub calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestBus {
+    /// Starts the bus and the player; `None` when `dbus-daemon` or `busctl`
+    /// is not installed.
+    pub fn start() -> Option<Self> {
+        let busctl = Command::new("busctl").arg("--version").output();
+        if !busctl.is_ok_and(|output| output.status.success()) {
+            return None;
+        }
+
+        let socket_dir = env::temp_dir().join(format!("aexclock-bus-{}", std::process::id()));
+        std::fs::create_dir_all(&socket_dir).ok()?;
+        let socket = socket_dir.join("bus");
+        let _ = std::fs::remove_file(&socket);
+
+        let mut daemon = Command::new("dbus-daemon")
+            .arg("--session")
+            .arg(format!("--address=unix:path={}", socket.display()))
+            .args(["--print-address", "--nofork"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+
+        // the address is printed once the daemon listens
+        let mut address = String::new();
+        let stdout = daemon.stdout.take()?;
+        BufReader::new(stdout).read_line(&mut address).ok()?;
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let bus = Self {
+            daemon,
+            socket_dir,
+            address: address.trim().to_string(),
+            calls: calls.clone(),
+        };
+
+        let stream = UnixStream::connect(&socket).ok()?;
+        let (ready_tx, ready_rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = Player::new(stream, calls).and_then(|mut player| player.serve(&ready_tx));
+        });
+        ready_rx.recv().ok()?;
+
+        Some(bus)
+    }
+}
+
+impl Drop for TestBus {
+    fn drop(&mut self) {
+        // the player thread ends when its connection closes
+        let _ = self.daemon.kill();
+        let _ = self.daemon.wait();
+        let _ = std::fs::remove_dir_all(&self.socket_dir);
+    }
+}
+
+// ====== Player ======
+
+/// A method call read from the bus.
+struct Call {
+    serial: u32,
+    sender: String,
+    member: String,
+    body: Vec<u8>,
+}
+
+struct Player {
+    stream: UnixStream,
+    serial: u32,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl Player {
+    fn new(mut stream: UnixStream, calls: Arc<Mutex<Vec<String>>>) -> std::io::Result<Self> {
+        // EXTERNAL with an empty identity: the daemon checks the socket credentials
+        stream.write_all(b"\0AUTH EXTERNAL\r\n")?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        if line.starts_with("DATA") {
+            stream.write_all(b"DATA\r\n")?;
+            line.clear();
+            reader.read_line(&mut line)?;
+        }
+        if !line.starts_with("OK") {
+            return Err(std::io::Error::other(line));
+        }
+        stream.write_all(b"BEGIN\r\n")?;
+
+        Ok(Self {
+            stream,
+            serial: 0,
+            calls,
+        })
+    }
+
+    /// Takes the player name, then answers calls until the bus goes away.
+    fn serve(&mut self, ready_tx: &mpsc::Sender<()>) -> std::io::Result<()> {
+        self.call_bus("Hello", None, &Body::default())?;
+        let mut args = Body::default();
+        args.string(PLAYER_NAME).u32(0);
+        let request = self.call_bus("RequestName", Some("su"), &args)?;
+
+        loop {
+            let (kind, reply_serial, call) = self.read()?;
+            if kind == METHOD_RETURN && reply_serial == Some(request) {
+                let _ = ready_tx.send(());
+            }
+            if kind == METHOD_CALL {
+                self.answer(&call)?;
+            }
+        }
+    }
+
+    fn answer(&mut self, call: &Call) -> std::io::Result<()> {
+        match call.member.as_str() {
+            "Get" => {
+                let mut args = Reader::new(&call.body);
+                let _interface = args.string();
+                let mut body = Body::default();
+                match args.string().as_str() {
+                    "PlaybackStatus" => body.variant("s", |v| {
+                        v.string("Playing");
+                    }),
+                    "Position" => body.variant("x", |v| {
+                        v.i64(83_000_000);
+                    }),
+                    "Identity" => body.variant("s", |v| {
+                        v.string("Test Player");
+                    }),
+                    "Metadata" => body.variant("a{sv}", metadata),
+                    name => {
+                        return self.error(call, &format!("No property {}", name));
+                    }
+                };
+                self.reply(call, Some("v"), &body)
+            }
+            "PlayPause" | "Next" | "Previous" => {
+                self.calls.lock().unwrap().push(call.member.clone());
+                self.reply(call, None, &Body::default())
+            }
+            "Seek" => {
+                let offset = Reader::new(&call.body).i64();
+                self.calls.lock().unwrap().push(format!("Seek {}", offset));
+                self.reply(call, None, &Body::default())
+            }
+            _ => self.error(call, "Unknown method"),
+        }
+    }
+
+    fn call_bus(
+        &mut self,
+        member: &str,
+        signature: Option<&str>,
+        body: &Body,
+    ) -> std::io::Result<u32> {
+        let mut fields = Body::default();
+        fields
+            .field(1, "o", "/org/freedesktop/DBus")
+            .field(2, "s", "org.freedesktop.DBus")
+            .field(3, "s", member)
+            .field(6, "s", "org.freedesktop.DBus");
+        if let Some(signature) = signature {
+            fields.field(8, "g", signature);
+        }
+        self.send(METHOD_CALL, fields, body)
+    }
+
+    fn reply(&mut self, call: &Call, signature: Option<&str>, body: &Body) -> std::io::Result<()> {
+        let mut fields = Body::default();
+        fields.reply_serial(call.serial).field(6, "s", &call.sender);
+        if let Some(signature) = signature {
+            fields.field(8, "g", signature);
+        }
+        self.send(METHOD_RETURN, fields, body).map(|_| ())
+    }
+
+    fn error(&mut self, call: &Call, message: &str) -> std::io::Result<()> {
+        let mut fields = Body::default();
+        fields
+            .field(4, "s", "org.freedesktop.DBus.Error.Failed")
+            .reply_serial(call.serial)
+            .field(6, "s", &call.sender)
+            .field(8, "g", "s");
+        let mut body = Body::default();
+        body.string(message);
+        self.send(ERROR, fields, &body).map(|_| ())
+    }
+
+    /// Writes a little-endian message; returns its serial.
+    fn send(&mut self, kind: u8, fields: Body, body: &Body) -> std::io::Result<u32> {
+        self.serial += 1;
+        let mut message = Body::default();
+        message.u8(b'l').u8(kind).u8(0).u8(1);
+        message.u32(body.0.len() as u32).u32(self.serial);
+        message.u32(fields.0.len() as u32);
+        message.0.extend(&fields.0);
+        message.align(8);
+        message.0.extend(&body.0);
+        self.stream.write_all(&message.0)?;
+        Ok(self.serial)
+    }
+
+    /// Reads one message: its type, the serial it replies to and the call.
+    fn read(&mut self) -> std::io::Result<(u8, Option<u32>, Call)> {
+        let mut fixed = [0u8; 16];
+        self.stream.read_exact(&mut fixed)?;
+        let number = |at: usize| u32::from_le_bytes(fixed[at..at + 4].try_into().unwrap());
+        let (body_len, serial, fields_len) = (number(4), number(8), number(12));
+
+        let padded = (fields_len as usize).next_multiple_of(8);
+        let mut fields = vec![0u8; padded];
+        self.stream.read_exact(&mut fields)?;
+        let mut body = vec![0u8; body_len as usize];
+        self.stream.read_exact(&mut body)?;
+
+        let mut call = Call {
+            serial,
+            sender: String::new(),
+            member: String::new(),
+            body,
+        };
+        let mut reply_serial = None;
+
+        // header fields start 16 bytes in, which keeps their 8-byte alignment
+        let mut reader = Reader::new(&fields[..fields_len as usize]);
+        while !reader.done() {
+            reader.align(8);
+            let code = reader.u8();
+            let signature = reader.signature();
+            match signature.as_str() {
+                "u" => {
+                    let value = reader.u32();
+                    if code == 5 {
+                        reply_serial = Some(value);
+                    }
+                }
+                "g" => {
+                    reader.signature();
+                }
+                _ => {
+                    let value = reader.string();
+                    match code {
+                        3 => call.member = value,
+                        7 => call.sender = value,
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        Ok((fixed[1], reply_serial, call))
+    }
+}
+
+/// `Metadata` of the playing track.
+fn metadata(body: &mut Body) {
+    body.array(8, |entries| {
+        entries.align(8);
+        entries.string("xesam:title");
+        entries.variant("s", |v| {
+            v.string("One More Time");
+        });
+        entries.align(8);
+        entries.string("xesam:artist");
+        entries.variant("as", |v| {
+            v.array(4, |artists| {
+                artists.string("Daft Punk");
+            });
+        });
+        entries.align(8);
+        entries.string("mpris:length");
+        entries.variant("x", |v| {
+            v.i64(320_000_000);
+        });
+    });
+}
+
+// ====== Marshalling ======
+
+/// Little-endian D-Bus values, aligned relative to the start of the buffer.
+#[derive(Default)]
+struct Body(Vec<u8>);
+
+impl Body {
+    fn align(&mut self, to: usize) -> &mut Self {
+        self.0.resize(self.0.len().next_multiple_of(to), 0);
+        self
+    }
+
+    fn u8(&mut self, value: u8) -> &mut Self {
+        self.0.push(value);
+        self
+    }
+
+    fn u32(&mut self, value: u32) -> &mut Self {
+        self.align(4).0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn i64(&mut self, value: i64) -> &mut Self {
+        self.align(8).0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn string(&mut self, value: &str) -> &mut Self {
+        self.u32(value.len() as u32);
+        self.0.extend(value.as_bytes());
+        self.u8(0)
+    }
+
+    fn signature(&mut self, value: &str) -> &mut Self {
+        self.u8(value.len() as u8);
+        self.0.extend(value.as_bytes());
+        self.u8(0)
+    }
+
+    fn variant(&mut self, signature: &str, value: impl FnOnce(&mut Self)) -> &mut Self {
+        self.signature(signature);
+        value(self);
+        self
+    }
+
+    /// An array whose elements are aligned to `element_align`.
+    fn array(&mut self, element_align: usize, elements: impl FnOnce(&mut Self)) -> &mut Self {
+        self.u32(0);
+        let len_at = self.0.len() - 4;
+        self.align(element_align);
+        let start = self.0.len();
+        elements(self);
+        let len = (self.0.len() - start) as u32;
+        self.0[len_at..len_at + 4].copy_from_slice(&len.to_le_bytes());
+        self
+    }
+
+    /// A header field holding a string, object path or signature.
+    fn field(&mut self, code: u8, signature: &str, value: &str) -> &mut Self {
+        self.align(8).u8(code);
+        match signature {
+            "g" => self.variant("g", |v| {
+                v.signature(value);
+            }),
+            _ => self.variant(signature, |v| {
+                v.string(value);
+            }),
+        }
+    }
+
+    fn reply_serial(&mut self, serial: u32) -> &mut Self {
+        self.align(8).u8(5).variant("u", |v| {
+            v.u32(serial);
+        })
+    }
+}
+
+/// Reads little-endian D-Bus values.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, at: 0 }
+    }
+
+    fn done(&self) -> bool {
+        self.at >= self.bytes.len()
+    }
+
+    fn align(&mut self, to: usize) {
+        self.at = self.at.next_multiple_of(to);
+    }
+
+    fn u8(&mut self) -> u8 {
+        self.at += 1;
+        self.bytes[self.at - 1]
+    }
+
+    fn u32(&mut self) -> u32 {
+        self.align(4);
+        self.at += 4;
+        u32::from_le_bytes(self.bytes[self.at - 4..self.at].try_into().unwrap())
+    }
+
+    fn i64(&mut self) -> i64 {
+        self.align(8);
+        self.at += 8;
+        i64::from_le_bytes(self.bytes[self.at - 8..self.at].try_into().unwrap())
+    }
+
+    fn string(&mut self) -> String {
+        let len = self.u32() as usize;
+        self.text(len)
+    }
+
+    fn signature(&mut self) -> String {
+        let len = self.u8() as usize;
+        self.text(len)
+    }
+
+    fn text(&mut self, len: usize) -> String {
+        let text = String::from_utf8_lossy(&self.bytes[self.at..self.at + len]).to_string();
+        // skip the trailing nul
+        self.at += len + 1;
+        text
+    }
+}
@@ -1,3 +1,5 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono_tz::Tz;
 use color_eyre::eyre::{Result, eyre};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use once_cell::sync::OnceCell;
@@ -6,11 +8,18 @@ use ratatui::widgets::BorderType;
 use serde::Deserialize;
 use std::convert::TryFrom;
 use std::fs;
+use std::path::PathBuf;
 
 #[derive(Debug)]
 pub struct Config {
     pub themes: Theme,
     pub keybinds: Keybinds,
+    pub clock: ClockConfig,
+    pub world_clock: WorldClockConfig,
+    pub alarms: AlarmsConfig,
+    pub pomodoro: PomodoroConfig,
+    pub calendar: CalendarConfig,
+    pub audio: AudioConfig,
 }
 
 #[derive(Debug)]
@@ -87,6 +96,248 @@ pub struct Keybinds {
     pub info: String,
     pub cancel: String,
     pub quit: String,
+    #[serde(default = "default_toggle_key")]
+    pub toggle: String,
+    #[serde(default = "default_delete_key")]
+    pub delete: String,
+    #[serde(default = "default_solo_key")]
+    pub solo: String,
+    #[serde(default = "default_profiles_key")]
+    pub profiles: String,
+    /// held with `content_left/right` to change the balance instead of the volume
+    #[serde(default = "default_balance_modifier")]
+    pub balance_modifier: String,
+    /// held with `content_left/right` to seek in the Media panel
+    #[serde(default = "default_seek_modifier")]
+    pub seek_modifier: String,
+    /// held with `content_left/right` to change the volume by `fine_step`
+    #[serde(default = "default_fine_modifier")]
+    pub fine_modifier: String,
+    #[serde(default = "default_disconnect_key")]
+    pub disconnect: String,
+    /// turns the wifi radio on or off
+    #[serde(default = "default_radio_key")]
+    pub radio: String,
+    #[serde(default = "default_rescan_key")]
+    pub rescan: String,
+}
+
+fn default_toggle_key() -> String {
+    "space".to_string()
+}
+
+fn default_delete_key() -> String {
+    "delete".to_string()
+}
+
+fn default_solo_key() -> String {
+    "s".to_string()
+}
+
+fn default_profiles_key() -> String {
+    "p".to_string()
+}
+
+fn default_balance_modifier() -> String {
+    "alt".to_string()
+}
+
+fn default_seek_modifier() -> String {
+    "alt".to_string()
+}
+
+fn default_fine_modifier() -> String {
+    "ctrl".to_string()
+}
+
+fn default_disconnect_key() -> String {
+    "d".to_string()
+}
+
+fn default_radio_key() -> String {
+    "w".to_string()
+}
+
+fn default_rescan_key() -> String {
+    "r".to_string()
+}
+
+/// Names of the built-in clock fonts; `clock_font` builds them in this order.
+pub const BUILTIN_FONT_NAMES: [&str; 6] = [
+    "double",
+    "block",
+    "small",
+    "thin",
+    "seven-segment",
+    "braille",
+];
+
+/// Options for the big clock, read from the optional `[clock]` section.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ClockConfig {
+    pub show_seconds: bool,
+    pub twelve_hour: bool,
+    pub show_date: bool,
+    /// chrono strftime format of the date line (e.g. "%A, %d %B %Y")
+    pub date_format: String,
+    /// name of the big-digit font, or "auto" for the largest one that fits a third of the terminal
+    pub font: String,
+    /// font file inside `~/.config/aex/` that is offered next to the built-in fonts
+    pub custom_font: Option<String>,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            show_seconds: false,
+            twelve_hour: false,
+            show_date: false,
+            date_format: "%A, %d %B %Y".to_string(),
+            font: "auto".to_string(),
+            custom_font: None,
+        }
+    }
+}
+
+/// Where the world clock is shown.
+#[derive(Debug, PartialEq)]
+pub enum WorldClockPosition {
+    /// compact line under the big clock
+    Clock,
+    /// own item in the content menu
+    Menu,
+}
+
+#[derive(Debug)]
+pub struct WorldClockConfig {
+    pub position: WorldClockPosition,
+    pub zones: Vec<WorldClockZone>,
+}
+
+#[derive(Debug)]
+pub struct WorldClockZone {
+    pub label: String,
+    pub tz: Tz,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct RawWorldClock {
+    pub position: String,
+    pub zones: Vec<RawWorldClockZone>,
+}
+
+impl Default for RawWorldClock {
+    fn default() -> Self {
+        Self {
+            position: "menu".to_string(),
+            zones: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RawWorldClockZone {
+    pub label: String,
+    pub tz: String,
+}
+
+impl TryFrom<RawWorldClock> for WorldClockConfig {
+    type Error = color_eyre::eyre::Report;
+
+    fn try_from(raw: RawWorldClock) -> Result<Self> {
+        let position = match raw.position.to_lowercase().as_str() {
+            "clock" => WorldClockPosition::Clock,
+            "menu" => WorldClockPosition::Menu,
+            _ => return Err(eyre!("Invalid world clock position: {}", raw.position)),
+        };
+
+        let zones = raw
+            .zones
+            .into_iter()
+            .map(|zone| {
+                let tz = zone
+                    .tz
+                    .parse::<Tz>()
+                    .map_err(|_| eyre!("Invalid time zone: {}", zone.tz))?;
+                Ok(WorldClockZone {
+                    label: zone.label,
+                    tz,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { position, zones })
+    }
+}
+
+/// Options for alarms, read from the optional `[alarms]` section.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct AlarmsConfig {
+    pub snooze_minutes: u32,
+}
+
+impl Default for AlarmsConfig {
+    fn default() -> Self {
+        Self { snooze_minutes: 5 }
+    }
+}
+
+/// Phase lengths for pomodoro mode, read from the optional `[pomodoro]` section.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct PomodoroConfig {
+    pub work_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    /// work sessions before a long break
+    pub sessions_per_cycle: u32,
+}
+
+impl Default for PomodoroConfig {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            sessions_per_cycle: 4,
+        }
+    }
+}
+
+/// Options for the calendar, read from the optional `[calendar]` section.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CalendarConfig {
+    /// `.ics` file with events, absolute or relative to `~/.config/aex/`
+    pub ics_file: Option<String>,
+}
+
+/// Highest volume anything in aexClock sets, in percent.
+pub const VOLUME_LIMIT: u8 = 150;
+
+/// Volume steps and limit for the Audio panel, read from the optional `[audio]` section.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct AudioConfig {
+    /// percent per `content_left/right` press
+    pub volume_step: u8,
+    /// percent per press with `fine_modifier` held
+    pub fine_step: u8,
+    /// highest volume the panel sets, up to 150 to over-amplify
+    pub max_volume: u8,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            volume_step: 5,
+            fine_step: 1,
+            max_volume: 100,
+        }
+    }
 }
 
 // RawConfig mirrors the toml, to parse before converting themes to strong types
@@ -94,20 +345,45 @@ pub struct Keybinds {
 pub struct RawConfig {
     pub themes: RawThemes,
     pub keybinds: Keybinds,
+    #[serde(default)]
+    pub clock: ClockConfig,
+    #[serde(default)]
+    pub world_clock: RawWorldClock,
+    #[serde(default)]
+    pub alarms: AlarmsConfig,
+    #[serde(default)]
+    pub pomodoro: PomodoroConfig,
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+    #[serde(default)]
+    pub audio: AudioConfig,
 }
 
 impl Config {
     pub fn load() -> Result<Self> {
-        let mut config_path =
-            dirs::config_dir().ok_or_else(|| eyre!("Could not find config directory"))?;
-        config_path.push("aex");
+        let mut config_path = aex_config_dir()?;
         config_path.push("clock.toml");
 
         let config_str = fs::read_to_string(&config_path)?;
-        let raw: RawConfig = toml::from_str(&config_str)?;
+        Self::parse(&config_str)
+    }
+
+    /// Parses and validates the contents of `clock.toml`.
+    fn parse(config_str: &str) -> Result<Self> {
+        let raw: RawConfig = toml::from_str(config_str)?;
+        validate_date_format(&raw.clock.date_format)?;
+        validate_font(&raw.clock)?;
+        validate_pomodoro(&raw.pomodoro)?;
+        validate_audio(&raw.audio)?;
         Ok(Config {
             themes: raw.themes.try_into()?,
             keybinds: raw.keybinds,
+            clock: raw.clock,
+            world_clock: raw.world_clock.try_into()?,
+            alarms: raw.alarms,
+            pomodoro: raw.pomodoro,
+            calendar: raw.calendar,
+            audio: raw.audio,
         })
     }
 
@@ -131,6 +407,7 @@ impl Config {
                 "esc" | "escape" => keycode = Some(KeyCode::Esc),
                 "tab" => keycode = Some(KeyCode::Tab),
                 "backspace" => keycode = Some(KeyCode::Backspace),
+                "delete" | "del" => keycode = Some(KeyCode::Delete),
                 "space" => keycode = Some(KeyCode::Char(' ')),
                 s if s.len() == 1 => {
                     let ch = s.chars().next().unwrap();
@@ -150,6 +427,23 @@ impl Config {
             None => false,
         }
     }
+
+    /// Like `key_matches`, with an extra modifier (e.g. "alt") held on top of the keybind
+    pub fn key_matches_with(
+        &self,
+        key_event: &KeyEvent,
+        keybind_str: &str,
+        modifier: &str,
+    ) -> bool {
+        self.key_matches(key_event, &format!("{}+{}", modifier, keybind_str))
+    }
+}
+
+/// Directory holding `clock.toml` and the other app files, `~/.config/aex/`.
+pub fn aex_config_dir() -> Result<PathBuf> {
+    let mut dir = dirs::config_dir().ok_or_else(|| eyre!("Could not find config directory"))?;
+    dir.push("aex");
+    Ok(dir)
 }
 
 static CONFIG_CELL: OnceCell<Config> = OnceCell::new();
@@ -162,6 +456,43 @@ pub fn init_config() -> Result<()> {
     Ok(())
 }
 
+/// Initializes the config for tests from the required sections only, leaving the rest at defaults.
+#[cfg(test)]
+pub fn init_test_config() {
+    const TEST_CONFIG: &str = r#"
+[themes]
+borders_on = true
+border_color = "darkgray"
+border_style = "rounded"
+nav_selected_fg_color = "black"
+nav_selected_bg_color = "cyan"
+content_selected_color = "cyan"
+bg_color = "black"
+fg_color = "white"
+scroll_color = "cyan"
+bar_side_color = "magenta"
+bar_filled_color = "cyan"
+bar_empty_color = "blue"
+bar_selected_side_color = "darkgray"
+bar_selected_filled_color = "white"
+bar_selected_empty_color = "gray"
+
+[keybinds]
+nav_up = "up"
+nav_down = "down"
+content_up = "shift+up"
+content_down = "shift+down"
+content_right = "shift+right"
+content_left = "shift+left"
+accept = "enter"
+info = "tab"
+cancel = "esc"
+quit = "q"
+"#;
+    CONFIG_CELL.get_or_init(|| Config::parse(TEST_CONFIG).expect("test config is valid"));
+}
+
+#[allow(non_snake_case)]
 pub fn CONFIG() -> &'static Config {
     CONFIG_CELL.get().expect("Config not initialized")
 }
@@ -199,3 +530,52 @@ fn parse_border(s: &str) -> Result<BorderType> {
         _ => Err(eyre!("Invalid border type: {}", s)),
     }
 }
+
+fn validate_date_format(s: &str) -> Result<()> {
+    if StrftimeItems::new(s).any(|item| matches!(item, Item::Error)) {
+        return Err(eyre!("Invalid date format: {}", s));
+    }
+    Ok(())
+}
+
+/// The name of a custom font is only known once it is loaded; `init_fonts` checks it then.
+fn validate_font(clock: &ClockConfig) -> Result<()> {
+    if clock.font == "auto"
+        || BUILTIN_FONT_NAMES.contains(&clock.font.as_str())
+        || clock.custom_font.is_some()
+    {
+        return Ok(());
+    }
+    Err(eyre!(
+        "Invalid clock font: {} (expected auto or one of {})",
+        clock.font,
+        BUILTIN_FONT_NAMES.join(", ")
+    ))
+}
+
+fn validate_pomodoro(pomodoro: &PomodoroConfig) -> Result<()> {
+    let values = [
+        pomodoro.work_minutes,
+        pomodoro.short_break_minutes,
+        pomodoro.long_break_minutes,
+        pomodoro.sessions_per_cycle,
+    ];
+    if values.contains(&0) {
+        return Err(eyre!("Pomodoro values must be greater than 0"));
+    }
+    Ok(())
+}
+
+fn validate_audio(audio: &AudioConfig) -> Result<()> {
+    if audio.volume_step == 0 || audio.fine_step == 0 {
+        return Err(eyre!("Audio volume steps must be greater than 0"));
+    }
+    if !(1..=VOLUME_LIMIT).contains(&audio.max_volume) {
+        return Err(eyre!(
+            "Audio max_volume must be between 1 and {}, got {}",
+            VOLUME_LIMIT,
+            audio.max_volume
+        ));
+    }
+    Ok(())
+}
@@ -1,12 +1,23 @@
+mod alert;
+mod audio_profiles;
+mod backend;
 mod config;
 mod widgets;
 use crate::{
-    config::{CONFIG, init_config},
-    widgets::{audio_mixer::AudioMixer, content_menu::StMenuItem, net_connect::NetConnect},
+    config::{CONFIG, WorldClockPosition, init_config},
+    widgets::{
+        alarms::Alarms, audio_mixer::AudioMixer, calendar::Calendar, clock_font::init_fonts,
+        content_menu::MenuWidget, media_player::MediaPlayer, net_connect::NetConnect,
+        pomodoro::Pomodoro, timers::Timers, world_clock::WorldClock,
+    },
+};
+use chrono::Local;
+use color_eyre::{
+    Result,
+    eyre::{Error, eyre},
 };
-use color_eyre::{Result, eyre::Error};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode},
 };
@@ -15,14 +26,25 @@ use ratatui::{
     layout::{Constraint, Direction, Layout},
 };
 use std::{
-    sync::{Arc, Mutex},
+    env,
     time::{Duration, Instant},
 };
 use widgets::clock::ClockWidget;
 use widgets::content_menu::ContentMenu;
 
 fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let profile = match args.as_slice() {
+        [] => None,
+        [flag, name] if flag == "--profile" => Some(name),
+        _ => return Err(eyre!("Usage: aexClock [--profile <name>]")),
+    };
+
     init_config()?;
+    if let Some(name) = profile {
+        return apply_profile(name);
+    }
+    init_fonts()?;
 
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
@@ -38,8 +60,32 @@ fn main() -> Result<()> {
     result
 }
 
+/// Apply a saved audio profile without starting the UI, capped at `[audio] max_volume`.
+fn apply_profile(name: &str) -> Result<()> {
+    let applied = audio_profiles::apply_by_name(
+        name,
+        backend::audio::from_env().as_ref(),
+        CONFIG().audio.max_volume,
+    )?;
+    println!("Applied audio profile {} to {} stream(s)", name, applied);
+    Ok(())
+}
+
 fn run(mut terminal: DefaultTerminal) -> Result<()> {
-    let items = vec![make_netconnect_menu_item(), make_audiomixer_menu_item()];
+    let mut items: Vec<Box<dyn MenuWidget>> = vec![
+        Box::new(NetConnect::new()),
+        Box::new(AudioMixer::new()),
+        Box::new(MediaPlayer::new()),
+        Box::new(Alarms::load()?),
+        Box::new(Timers::new()),
+        Box::new(Pomodoro::new()),
+        Box::new(Calendar::new()),
+    ];
+
+    let world_clock = &CONFIG().world_clock;
+    if world_clock.position == WorldClockPosition::Menu && !world_clock.zones.is_empty() {
+        items.push(Box::new(WorldClock::new()));
+    }
     let mut content_menu = ContentMenu::new(items);
 
     let tick_rate = Duration::from_secs(1);
@@ -62,6 +108,9 @@ fn run(mut terminal: DefaultTerminal) -> Result<()> {
         }
 
         if last_tick.elapsed() >= tick_rate {
+            content_menu.tick();
+            alert::tick(Local::now());
+
             terminal.draw(|f| {
                 render(f, &content_menu);
             })?;
@@ -75,7 +124,10 @@ fn run(mut terminal: DefaultTerminal) -> Result<()> {
 fn render(frame: &mut Frame, menu: &ContentMenu) {
     let clock_frame = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(6), Constraint::Min(0)])
+        .constraints([
+            Constraint::Length(ClockWidget::height(frame.area())),
+            Constraint::Min(0),
+        ])
         .split(frame.area());
 
     let menu_frame = Layout::default()
@@ -90,60 +142,29 @@ fn render(frame: &mut Frame, menu: &ContentMenu) {
 fn dispatch_events(menu: &mut ContentMenu) -> Result<bool, Error> {
     let event = event::read()?;
 
+    // an active alert takes accept/cancel before the menu does
+    if let Event::Key(key_event) = &event
+        && alert::handle_key_event(key_event)
+    {
+        return Ok(false);
+    }
+
+    // a panel taking text input gets every key, including quit
+    let captures_input = menu.captures_input();
     menu.handle_events(&event)?;
 
     match event {
-        Event::Key(key_event) => {
+        Event::Key(key_event) if !captures_input => {
             if key_event.kind != KeyEventKind::Press {
                 return Ok(false);
             }
 
             if CONFIG().key_matches(&key_event, &CONFIG().keybinds.quit) {
-                return Ok(true);
+                Ok(true)
             } else {
-                return Ok(false);
+                Ok(false)
             }
         }
-        _ => {
-            return Ok(false);
-        }
-    }
-}
-
-fn make_netconnect_menu_item() -> StMenuItem<'static> {
-    let event_nc = Arc::new(Mutex::new(NetConnect::new()));
-    let render_nc = event_nc.clone();
-    let refresh_nc = event_nc.clone();
-
-    StMenuItem {
-        title: "Internet".into(),
-        event: Box::new(move |event: &Event| {
-            event_nc.lock().unwrap().handle_events(&event)?;
-            Ok(())
-        }),
-        starter: Box::new(move || {
-            NetConnect::start_auto_refresh(refresh_nc.clone());
-            Ok(())
-        }),
-        render: Box::new(move |area| render_nc.lock().unwrap().get_widget(area)),
-    }
-}
-
-fn make_audiomixer_menu_item() -> StMenuItem<'static> {
-    let event_am = Arc::new(Mutex::new(AudioMixer::new()));
-    let render_am = event_am.clone();
-    let refresh_am = event_am.clone();
-
-    StMenuItem {
-        title: "Audio".into(),
-        event: Box::new(move |event: &Event| {
-            event_am.lock().unwrap().handle_events(&event)?;
-            Ok(())
-        }),
-        starter: Box::new(move || {
-            AudioMixer::start_auto_refresh(refresh_am.clone());
-            Ok(())
-        }),
-        render: Box::new(move |area| render_am.lock().unwrap().get_widget(area)),
+        _ => Ok(false),
     }
 }
@@ -0,0 +1,766 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, TimeDelta, TimeZone};
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, eyre},
+};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, Padding, Paragraph},
+};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::{
+    alert,
+    config::{CONFIG, aex_config_dir},
+    widgets::content_menu::{
+        EnContentMenuItem, MenuWidget, WiMenuItem, overlay_block, overlay_rect,
+    },
+};
+
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+const WEEKDAY_LETTERS: [&str; 7] = ["M", "T", "W", "T", "F", "S", "S"];
+
+/// Longest stretch of local time a DST change skips.
+const MAX_DST_GAP_MINUTES: i64 = 120;
+
+/// A single alarm. `days` is a weekday mask (bit 0 = Monday); an empty mask fires once.
+#[derive(Debug, Clone)]
+pub struct Alarm {
+    pub label: String,
+    pub time: NaiveTime,
+    pub days: u8,
+    pub enabled: bool,
+}
+
+// RawAlarm mirrors one `[[alarms]]` entry of `~/.config/aex/alarms.toml`
+#[derive(Debug, Serialize, Deserialize)]
+struct RawAlarm {
+    label: String,
+    time: String,
+    #[serde(default)]
+    days: Vec<String>,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RawAlarmFile {
+    #[serde(default)]
+    alarms: Vec<RawAlarm>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl TryFrom<RawAlarm> for Alarm {
+    type Error = color_eyre::eyre::Report;
+
+    fn try_from(raw: RawAlarm) -> Result<Self> {
+        let time = NaiveTime::parse_from_str(&raw.time, "%H:%M")
+            .map_err(|_| eyre!("Invalid alarm time: {}", raw.time))?;
+
+        let mut days = 0;
+        for day in &raw.days {
+            let index = WEEKDAYS
+                .iter()
+                .position(|d| d.eq_ignore_ascii_case(day))
+                .ok_or_else(|| eyre!("Invalid alarm day: {}", day))?;
+            days |= 1 << index;
+        }
+
+        Ok(Self {
+            label: raw.label,
+            time,
+            days,
+            enabled: raw.enabled,
+        })
+    }
+}
+
+impl From<&Alarm> for RawAlarm {
+    fn from(alarm: &Alarm) -> Self {
+        Self {
+            label: alarm.label.clone(),
+            time: alarm.time.format("%H:%M").to_string(),
+            days: WEEKDAYS
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| alarm.days & (1 << i) != 0)
+                .map(|(_, d)| d.to_string())
+                .collect(),
+            enabled: alarm.enabled,
+        }
+    }
+}
+
+impl Alarm {
+    fn repeats_on(&self, weekday: usize) -> bool {
+        self.days == 0 || self.days & (1 << weekday) != 0
+    }
+
+    /// Whether the alarm went off in `(since, now]`. A time skipped by a DST
+    /// change goes off at the first valid instant after it.
+    fn is_due<Tz: TimeZone>(&self, since: &DateTime<Tz>, now: &DateTime<Tz>) -> bool {
+        let tz = now.timezone();
+        let mut dates = vec![since.date_naive()];
+        if now.date_naive() != since.date_naive() {
+            dates.push(now.date_naive());
+        }
+
+        dates.into_iter().any(|date: NaiveDate| {
+            let local = date.and_time(self.time);
+            let Some(at) = (0..=MAX_DST_GAP_MINUTES).find_map(|minutes| {
+                tz.from_local_datetime(&(local + TimeDelta::minutes(minutes)))
+                    .earliest()
+            }) else {
+                return false;
+            };
+            *since < at
+                && at <= *now
+                && self.repeats_on(date.weekday().num_days_from_monday() as usize)
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum AlarmField {
+    Time,
+    Days,
+    Label,
+}
+
+/// State of the create/edit overlay.
+#[derive(Clone)]
+struct AlarmForm {
+    /// index of the alarm being edited, `None` for a new one
+    editing: Option<usize>,
+    /// typed digits, read right to left as HHMM
+    time_digits: String,
+    days: u8,
+    day_cursor: usize,
+    label: String,
+    field: AlarmField,
+    error: Option<String>,
+}
+
+/// Alarms panel: lists, edits and fires the alarms stored in `alarms.toml`.
+pub struct Alarms {
+    alarms: Vec<Alarm>,
+    selected: usize,
+    form: Option<AlarmForm>,
+    last_check: DateTime<Local>,
+    status: String,
+}
+
+impl Alarms {
+    // ====== Initialization ======
+
+    /// Load the alarms from `~/.config/aex/alarms.toml`; a missing file means no alarms.
+    pub fn load() -> Result<Self> {
+        let path = Self::file_path()?;
+
+        let alarms = if path.exists() {
+            let alarms_str = fs::read_to_string(&path)?;
+            let raw: RawAlarmFile = toml::from_str(&alarms_str)
+                .wrap_err_with(|| format!("Failed to parse {}", path.display()))?;
+            raw.alarms
+                .into_iter()
+                .map(Alarm::try_from)
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            alarms,
+            selected: 0,
+            form: None,
+            last_check: Local::now(),
+            status: String::new(),
+        })
+    }
+
+    // ====== Public Interface Methods ======
+
+    /// Raise an alert for every enabled alarm that went off since the last check.
+    /// Called from the main loop tick.
+    pub fn check_due(&mut self, now: DateTime<Local>) {
+        let since = self.last_check;
+        self.last_check = now;
+
+        if self.fire_due(&since, &now) {
+            self.save();
+        }
+    }
+
+    /// Raise the alerts of the alarms due in `(since, now]` and disable the
+    /// one-shot ones among them; returns whether any alarm was disabled.
+    fn fire_due<Tz: TimeZone>(&mut self, since: &DateTime<Tz>, now: &DateTime<Tz>) -> bool {
+        let mut changed = false;
+        for alarm in self.alarms.iter_mut().filter(|a| a.enabled) {
+            if !alarm.is_due(since, now) {
+                continue;
+            }
+
+            let label = if alarm.label.is_empty() {
+                "Alarm"
+            } else {
+                &alarm.label
+            };
+            alert::raise(&format!("{} {}", label, alarm.time.format("%H:%M")));
+
+            if alarm.days == 0 {
+                alarm.enabled = false;
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    // ====== Input Handling ======
+
+    fn handle_key_event(&mut self, key_event: &KeyEvent) {
+        if key_event.kind != KeyEventKind::Press {
+            return;
+        }
+
+        if self.form.is_some() {
+            self.handle_form_key_event(key_event);
+            return;
+        }
+
+        let c = CONFIG();
+
+        if c.key_matches(key_event, &c.keybinds.content_up) {
+            self.move_selected_up();
+        } else if c.key_matches(key_event, &c.keybinds.content_down) {
+            self.move_selected_down();
+        } else if c.key_matches(key_event, &c.keybinds.accept) {
+            self.open_form();
+        } else if c.key_matches(key_event, &c.keybinds.toggle) {
+            self.toggle_selected();
+        } else if c.key_matches(key_event, &c.keybinds.delete) {
+            self.delete_selected();
+        }
+    }
+
+    fn handle_form_key_event(&mut self, key_event: &KeyEvent) {
+        let c = CONFIG();
+        let Some(form) = self.form.as_mut() else {
+            return;
+        };
+
+        if c.key_matches(key_event, &c.keybinds.accept) {
+            self.accept_form();
+            return;
+        } else if c.key_matches(key_event, &c.keybinds.cancel) {
+            self.form = None;
+            return;
+        } else if c.key_matches(key_event, &c.keybinds.info) {
+            form.field = match form.field {
+                AlarmField::Time => AlarmField::Days,
+                AlarmField::Days => AlarmField::Label,
+                AlarmField::Label => AlarmField::Time,
+            };
+            return;
+        }
+
+        match form.field {
+            AlarmField::Time => match key_event.code {
+                KeyCode::Backspace => {
+                    form.time_digits.pop();
+                }
+                KeyCode::Char(ch) if ch.is_ascii_digit() => {
+                    if form.time_digits.len() == 4 {
+                        form.time_digits.remove(0);
+                    }
+                    form.time_digits.push(ch);
+                }
+                _ => {}
+            },
+            AlarmField::Days => {
+                if c.key_matches(key_event, &c.keybinds.content_left) {
+                    form.day_cursor = (form.day_cursor + 6) % 7;
+                } else if c.key_matches(key_event, &c.keybinds.content_right) {
+                    form.day_cursor = (form.day_cursor + 1) % 7;
+                } else if c.key_matches(key_event, &c.keybinds.toggle)
+                    || c.key_matches(key_event, &c.keybinds.content_up)
+                    || c.key_matches(key_event, &c.keybinds.content_down)
+                {
+                    form.days ^= 1 << form.day_cursor;
+                }
+            }
+            AlarmField::Label => match key_event.code {
+                KeyCode::Backspace => {
+                    form.label.pop();
+                }
+                KeyCode::Char(ch) => form.label.push(ch),
+                _ => {}
+            },
+        }
+    }
+
+    fn move_selected_down(&mut self) {
+        // the last row is "+ New alarm"
+        self.selected += 1;
+        if self.selected > self.alarms.len() {
+            self.selected = 0;
+        }
+    }
+
+    fn move_selected_up(&mut self) {
+        if self.selected == 0 {
+            self.selected = self.alarms.len();
+        } else {
+            self.selected -= 1;
+        }
+    }
+
+    fn open_form(&mut self) {
+        let form = match self.alarms.get(self.selected) {
+            Some(alarm) => AlarmForm {
+                editing: Some(self.selected),
+                time_digits: alarm.time.format("%H%M").to_string(),
+                days: alarm.days,
+                day_cursor: 0,
+                label: alarm.label.clone(),
+                field: AlarmField::Time,
+                error: None,
+            },
+            None => AlarmForm {
+                editing: None,
+                time_digits: String::new(),
+                days: 0,
+                day_cursor: 0,
+                label: String::new(),
+                field: AlarmField::Time,
+                error: None,
+            },
+        };
+
+        self.form = Some(form);
+    }
+
+    fn accept_form(&mut self) {
+        let Some(form) = self.form.as_mut() else {
+            return;
+        };
+
+        let digits = format!("{:0>4}", form.time_digits);
+        let time = match NaiveTime::parse_from_str(&digits, "%H%M") {
+            Ok(time) => time,
+            Err(_) => {
+                form.error = Some(format!("Invalid time {}:{}", &digits[..2], &digits[2..]));
+                return;
+            }
+        };
+
+        let alarm = Alarm {
+            label: form.label.trim().to_string(),
+            time,
+            days: form.days,
+            enabled: true,
+        };
+
+        match form.editing {
+            Some(index) if index < self.alarms.len() => self.alarms[index] = alarm,
+            _ => {
+                self.alarms.push(alarm);
+                self.selected = self.alarms.len() - 1;
+            }
+        }
+
+        self.form = None;
+        self.save();
+    }
+
+    fn toggle_selected(&mut self) {
+        if let Some(alarm) = self.alarms.get_mut(self.selected) {
+            alarm.enabled = !alarm.enabled;
+            self.save();
+        }
+    }
+
+    fn delete_selected(&mut self) {
+        if self.selected < self.alarms.len() {
+            self.alarms.remove(self.selected);
+            self.save();
+        }
+    }
+
+    // ====== Rendering UI Components ======
+
+    fn make_alarm_widget_list(&self, max_width: usize) -> List<'static> {
+        let mut items: Vec<Line> = Vec::new();
+
+        if !self.status.is_empty() {
+            items.push(Line::from(Span::styled(
+                self.status.clone(),
+                Style::default().fg(Color::Red),
+            )));
+            items.push(Line::from(""));
+        }
+
+        for (i, alarm) in self.alarms.iter().enumerate() {
+            let mut line = self.make_alarm_line(alarm, max_width);
+
+            line = if i == self.selected {
+                line.style(CONFIG().themes.content_selected_color)
+            } else {
+                line.style(CONFIG().themes.fg_color)
+            };
+
+            if !alarm.enabled {
+                line = line.patch_style(Style::default().add_modifier(Modifier::DIM));
+            }
+
+            items.push(line);
+        }
+
+        let new_style = if self.selected == self.alarms.len() {
+            CONFIG().themes.content_selected_color
+        } else {
+            CONFIG().themes.fg_color
+        };
+        items.push(Line::from("+ New alarm").style(new_style));
+
+        let theme = &CONFIG().themes;
+
+        let block = Block::default()
+            .borders(if theme.borders_on {
+                Borders::ALL
+            } else {
+                Borders::NONE
+            })
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.border_color))
+            .padding(Padding {
+                left: 1,
+                right: 1,
+                top: 0,
+                bottom: 0,
+            });
+
+        List::new(items).block(block)
+    }
+
+    fn make_alarm_line(&self, alarm: &Alarm, max_width: usize) -> Line<'static> {
+        let enabled = if alarm.enabled { "[x]" } else { "[ ]" };
+        let days = Self::format_days(alarm.days);
+
+        let line = format!(
+            "{} {}  {}  {}",
+            enabled,
+            alarm.time.format("%H:%M"),
+            days,
+            alarm.label
+        );
+
+        Line::from(line.chars().take(max_width).collect::<String>())
+    }
+
+    fn format_days(days: u8) -> String {
+        if days == 0 {
+            return format!("{:<13}", "once");
+        }
+
+        WEEKDAY_LETTERS
+            .iter()
+            .enumerate()
+            .map(|(i, letter)| if days & (1 << i) != 0 { *letter } else { "·" })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn make_form(
+        &self,
+        form: &AlarmForm,
+        max_width: usize,
+        area: Rect,
+    ) -> (EnContentMenuItem<'static>, Rect) {
+        let theme = &CONFIG().themes;
+        let keybinds = &CONFIG().keybinds;
+
+        let label_style = |field: AlarmField| {
+            if form.field == field {
+                Style::default().fg(theme.content_selected_color)
+            } else {
+                Style::default().fg(theme.fg_color)
+            }
+        };
+
+        let digits = format!("{:0>4}", form.time_digits);
+        let time_line = Line::from(vec![
+            Span::styled("Time   ", label_style(AlarmField::Time)),
+            Span::raw(format!("{}:{}", &digits[..2], &digits[2..])),
+        ]);
+
+        let mut day_spans = vec![Span::styled("Days   ", label_style(AlarmField::Days))];
+        for (i, letter) in WEEKDAY_LETTERS.iter().enumerate() {
+            let mut style = if form.days & (1 << i) != 0 {
+                Style::default().fg(theme.fg_color)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            if form.field == AlarmField::Days && form.day_cursor == i {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            day_spans.push(Span::styled(letter.to_string(), style));
+            day_spans.push(Span::raw(" "));
+        }
+        if form.days == 0 {
+            day_spans.push(Span::styled("(once)", Style::default().fg(Color::DarkGray)));
+        }
+
+        let cursor = if form.field == AlarmField::Label {
+            "_"
+        } else {
+            ""
+        };
+        let label_line = Line::from(vec![
+            Span::styled("Label  ", label_style(AlarmField::Label)),
+            Span::raw(format!("{}{}", form.label, cursor)),
+        ]);
+
+        let hint_line = Line::from(Span::styled(
+            format!(
+                "{}: next field  {}: save  {}: cancel",
+                keybinds.info, keybinds.accept, keybinds.cancel
+            ),
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        let mut lines = vec![time_line, Line::from(day_spans), label_line, hint_line];
+        if let Some(error) = &form.error {
+            lines.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(Color::Red),
+            )));
+        }
+
+        let title = if form.editing.is_some() {
+            "Edit alarm"
+        } else {
+            "New alarm"
+        };
+
+        let paragraph = Paragraph::new(lines).block(overlay_block().title(title));
+
+        (
+            EnContentMenuItem::Paragraph(paragraph),
+            overlay_rect(area, max_width as u16, 7, 2),
+        )
+    }
+
+    fn make_empty_prompt(&self) -> (EnContentMenuItem<'static>, Rect) {
+        (
+            EnContentMenuItem::Paragraph(
+                Paragraph::new("").style(Style::default().bg(CONFIG().themes.bg_color)),
+            ),
+            Rect::default(),
+        )
+    }
+
+    // ====== Storage ======
+
+    fn file_path() -> Result<PathBuf> {
+        let mut path = aex_config_dir()?;
+        path.push("alarms.toml");
+        Ok(path)
+    }
+
+    /// Write the alarms back to disk; failures are shown in the panel.
+    fn save(&mut self) {
+        self.status = match self.write_file() {
+            Ok(()) => String::new(),
+            Err(e) => format!("Failed to save alarms: {}", e),
+        };
+    }
+
+    fn write_file(&self) -> Result<()> {
+        let path = Self::file_path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let raw = RawAlarmFile {
+            alarms: self.alarms.iter().map(RawAlarm::from).collect(),
+        };
+        fs::write(&path, toml::to_string(&raw)?)?;
+        Ok(())
+    }
+}
+
+impl MenuWidget for Alarms {
+    fn title(&self) -> &str {
+        "Alarms"
+    }
+
+    /// Handle input events (keys only).
+    fn handle_event(&mut self, event: &Event) -> Result<()> {
+        match event {
+            Event::Key(key_event) => {
+                self.handle_key_event(key_event);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Build the main and overlay widgets for rendering.
+    fn render(&self, area: Rect) -> WiMenuItem<'static> {
+        let max_width = area.width.saturating_sub(4) as usize;
+
+        let list = self.make_alarm_widget_list(max_width);
+
+        let (overlay, overlay_area) = match &self.form {
+            Some(form) => self.make_form(form, max_width, area),
+            None => self.make_empty_prompt(),
+        };
+
+        WiMenuItem {
+            content: EnContentMenuItem::List(list),
+            overlay,
+            overlay_area,
+            show_overlay: self.form.is_some(),
+        }
+    }
+
+    fn tick(&mut self) {
+        self.check_due(Local::now());
+    }
+
+    fn captures_input(&self) -> bool {
+        self.form.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::{Europe::Berlin, Tz};
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Tz> {
+        // March 2026: the 2nd is a Monday, DST starts on Sunday the 29th
+        Berlin
+            .with_ymd_and_hms(2026, 3, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn alarm(time: &str, days: u8) -> Alarm {
+        Alarm {
+            label: String::new(),
+            time: NaiveTime::parse_from_str(time, "%H:%M").unwrap(),
+            days,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn is_due_in_the_half_open_window() {
+        let alarm = alarm("07:00", 0);
+        assert!(alarm.is_due(&at(2, 6, 59), &at(2, 7, 0)));
+        assert!(!alarm.is_due(&at(2, 7, 0), &at(2, 7, 1)));
+        assert!(!alarm.is_due(&at(2, 6, 58), &at(2, 6, 59)));
+    }
+
+    #[test]
+    fn is_due_across_midnight() {
+        let early = alarm("00:00", 0);
+        let late = alarm("23:59", 0);
+        assert!(early.is_due(&at(2, 23, 59), &at(3, 0, 0)));
+        assert!(late.is_due(&at(2, 23, 58), &at(3, 0, 1)));
+        assert!(!late.is_due(&at(2, 23, 59), &at(3, 0, 1)));
+    }
+
+    #[test]
+    fn repeating_alarm_follows_the_weekday_mask() {
+        // Monday and Wednesday
+        let alarm = alarm("07:00", 0b101);
+        assert!(alarm.is_due(&at(2, 6, 59), &at(2, 7, 0)));
+        assert!(!alarm.is_due(&at(3, 6, 59), &at(3, 7, 0)));
+        assert!(alarm.is_due(&at(4, 6, 59), &at(4, 7, 0)));
+        assert!(alarm.repeats_on(0) && !alarm.repeats_on(6));
+    }
+
+    #[test]
+    fn time_skipped_by_dst_fires_when_the_clock_jumps() {
+        let alarm = alarm("02:30", 0);
+        assert!(alarm.is_due(&at(29, 1, 59), &at(29, 3, 0)));
+        assert!(!alarm.is_due(&at(29, 3, 0), &at(29, 3, 1)));
+    }
+
+    #[test]
+    fn one_shot_alarm_disables_after_firing() {
+        let mut alarms = Alarms {
+            alarms: vec![alarm("07:00", 0), alarm("07:00", 0b1)],
+            selected: 0,
+            form: None,
+            last_check: Local::now(),
+            status: String::new(),
+        };
+
+        assert!(alarms.fire_due(&at(2, 6, 59), &at(2, 7, 0)));
+        assert!(!alarms.alarms[0].enabled);
+        assert!(alarms.alarms[1].enabled);
+
+        // a repeating alarm alone changes nothing on disk
+        assert!(!alarms.fire_due(&at(9, 6, 59), &at(9, 7, 0)));
+    }
+
+    #[test]
+    fn alarm_file_round_trips() {
+        let alarms = vec![
+            Alarm {
+                label: "Work".to_string(),
+                ..alarm("06:45", 0b11111)
+            },
+            Alarm {
+                enabled: false,
+                ..alarm("23:05", 0)
+            },
+        ];
+
+        let raw = RawAlarmFile {
+            alarms: alarms.iter().map(RawAlarm::from).collect(),
+        };
+        let toml_str = toml::to_string(&raw).unwrap();
+        assert!(toml_str.contains(r#"days = ["mon", "tue", "wed", "thu", "fri"]"#));
+
+        let parsed: RawAlarmFile = toml::from_str(&toml_str).unwrap();
+        let parsed: Vec<Alarm> = parsed
+            .alarms
+            .into_iter()
+            .map(|raw| Alarm::try_from(raw).unwrap())
+            .collect();
+
+        assert_eq!(parsed.len(), 2);
+        for (parsed, alarm) in parsed.iter().zip(&alarms) {
+            assert_eq!(parsed.label, alarm.label);
+            assert_eq!(parsed.time, alarm.time);
+            assert_eq!(parsed.days, alarm.days);
+            assert_eq!(parsed.enabled, alarm.enabled);
+        }
+    }
+
+    #[test]
+    fn invalid_alarm_entries_are_rejected() {
+        let parse = |entry: &str| {
+            let raw: RawAlarmFile = toml::from_str(&format!("[[alarms]]\n{}", entry)).unwrap();
+            raw.alarms
+                .into_iter()
+                .map(Alarm::try_from)
+                .collect::<Result<Vec<_>>>()
+        };
+
+        assert!(parse("label = \"\"\ntime = \"7:00\"").is_ok());
+        assert!(parse("label = \"\"\ntime = \"25:00\"").is_err());
+        assert!(parse("label = \"\"\ntime = \"07:00\"\ndays = [\"mon\", \"xyz\"]").is_err());
+    }
+}
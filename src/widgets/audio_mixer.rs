@@ -1,167 +1,500 @@
 use color_eyre::Result;
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
-    symbols::bar,
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, List, Padding, Paragraph},
+    widgets::{Block, Borders, List, Padding, Paragraph},
 };
 use std::{
-    process::Command,
-    sync::{Arc, Mutex},
+    sync::{
+        Arc, Weak,
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    },
     thread::sleep,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use crate::{
+    audio_profiles::{self, AudioProfile},
+    backend::audio::{self, AudioBackend, AudioDevice, ChannelVolume, SinkInput},
     config::CONFIG,
-    widgets::content_menu::{EnContentMenuItem, WiMenuItem},
+    widgets::content_menu::{
+        EnContentMenuItem, MenuWidget, WiMenuItem, overlay_block, overlay_rect,
+    },
 };
 
-#[derive(Clone)]
+/// Refresh interval when the backend has no change events.
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Wait after a change event so a burst of events causes a single refresh.
+const EVENT_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Everything the refresh thread reads from the audio backend.
+struct AudioState {
+    sinks: Vec<AudioDevice>,
+    default_sink: String,
+    sources: Vec<AudioDevice>,
+    default_source: String,
+    sink_inputs: Vec<SinkInput>,
+}
+
+/// A command run by the refresh thread, e.g. setting a volume.
+type AudioCommand = Box<dyn FnOnce(&dyn AudioBackend) -> Result<()> + Send>;
+
+/// What the refresh thread waits for.
+enum RefreshMessage {
+    /// the backend reported a change
+    Changed,
+    /// the change subscription ended or is not supported
+    Unsubscribed,
+    Command(AudioCommand),
+}
+
+/// What the refresh thread sends to the panel.
+enum AudioUpdate {
+    State(Result<AudioState>),
+    /// why a volume, mute, default device, move or profile command failed
+    CommandFailed(String),
+}
+
+/// A selectable row of the mixer, indexing into its section.
+#[derive(Clone, Copy)]
+enum AudioRow {
+    Sink(usize),
+    Source(usize),
+    Stream(usize),
+}
+
+/// What the selected row shows, so a refresh that adds or removes rows keeps it selected.
+#[derive(Debug, PartialEq)]
+enum RowKey {
+    /// sink name
+    Sink(String),
+    /// source name
+    Source(String),
+    /// stream id
+    Stream(String),
+}
+
+/// Overlay for moving a stream to another output.
+struct MoveOverlay {
+    stream_id: String,
+    stream_name: String,
+    /// index into `AudioMixer::sinks`
+    selected_sink: usize,
+}
+
+/// Overlay listing the saved audio profiles.
+struct ProfilePicker {
+    profiles: Vec<AudioProfile>,
+    /// row 0 saves the current volumes, the rows after it are the profiles
+    selected: usize,
+    /// name being typed for a new profile
+    naming: Option<String>,
+}
+
+/// A soloed stream and the mute states of all streams from before the solo.
+struct Solo {
+    stream_id: String,
+    saved_mutes: Vec<(String, bool)>,
+}
+
+/// Audio panel: output devices, input devices and per-application streams.
 pub struct AudioMixer {
-    selected_audio: usize,
-    selected_id: String,
-    selected_volume: u8,
-    audio_list: Vec<(String, u8, String)>,
-    started_refresh: bool,
+    /// index over sinks, then sources, then streams
+    selected_row: usize,
+    sinks: Vec<AudioDevice>,
+    default_sink: String,
+    sources: Vec<AudioDevice>,
+    default_source: String,
+    audio_list: Vec<SinkInput>,
+    move_overlay: Option<MoveOverlay>,
+    /// id of the stream whose details are shown
+    info_stream: Option<String>,
+    solo: Option<Solo>,
+    profile_picker: Option<ProfilePicker>,
+    /// the refresh thread sends states and command failures here; `tick` applies them
+    update_rx: Receiver<AudioUpdate>,
+    /// commands for the refresh thread, which only holds a weak handle and
+    /// ends once this is dropped
+    message_tx: Arc<Sender<RefreshMessage>>,
+    /// the refresh thread's ends of both channels, taken when it starts
+    refresh_channels: Option<(Sender<AudioUpdate>, Receiver<RefreshMessage>)>,
+    backend: Arc<dyn AudioBackend>,
+    status: String,
 }
 
 impl AudioMixer {
+    /// Create a mixer using the backend selected by `AEX_AUDIO_BACKEND`.
     pub fn new() -> Self {
+        Self::with_backend(audio::from_env())
+    }
+
+    pub fn with_backend(backend: Arc<dyn AudioBackend>) -> Self {
+        let (update_tx, update_rx) = mpsc::channel();
+        let (message_tx, message_rx) = mpsc::channel();
+
         Self {
-            selected_audio: 0,
+            selected_row: 0,
+            sinks: Vec::new(),
+            default_sink: String::new(),
+            sources: Vec::new(),
+            default_source: String::new(),
             audio_list: Vec::new(),
-            started_refresh: false,
-            selected_volume: 0,
-            selected_id: String::new(),
+            move_overlay: None,
+            info_stream: None,
+            solo: None,
+            profile_picker: None,
+            update_rx,
+            message_tx: Arc::new(message_tx),
+            refresh_channels: Some((update_tx, message_rx)),
+            backend,
+            status: String::new(),
         }
     }
 
-    pub fn handle_events(&mut self, event: &Event) -> Result<()> {
-        match event {
-            Event::Key(key_event) => {
-                self.handle_key_event(&key_event);
-                Ok(())
+    // ====== Input Handling ======
+
+    fn handle_key_event(&mut self, key_event: &KeyEvent) {
+        if key_event.kind != KeyEventKind::Press {
+            return;
+        }
+
+        let c = CONFIG();
+
+        if self.move_overlay.is_some() {
+            self.handle_move_overlay_key(key_event);
+            return;
+        }
+
+        if self.profile_picker.is_some() {
+            self.handle_profile_picker_key(key_event);
+            return;
+        }
+
+        if self.info_stream.is_some() {
+            if c.key_matches(key_event, &c.keybinds.accept)
+                || c.key_matches(key_event, &c.keybinds.cancel)
+                || c.key_matches(key_event, &c.keybinds.info)
+            {
+                self.info_stream = None;
             }
-            _ => Ok(()),
+            return;
+        }
+
+        if c.key_matches_with(
+            key_event,
+            &c.keybinds.content_left,
+            &c.keybinds.balance_modifier,
+        ) {
+            self.add_balance(-5);
+        } else if c.key_matches_with(
+            key_event,
+            &c.keybinds.content_right,
+            &c.keybinds.balance_modifier,
+        ) {
+            self.add_balance(5);
+        } else if c.key_matches(key_event, &c.keybinds.content_up) {
+            self.move_selected_up();
+        } else if c.key_matches(key_event, &c.keybinds.content_down) {
+            self.move_selected_down();
+        } else if c.key_matches_with(
+            key_event,
+            &c.keybinds.content_left,
+            &c.keybinds.fine_modifier,
+        ) {
+            self.add_volume(c.audio.fine_step, false);
+        } else if c.key_matches_with(
+            key_event,
+            &c.keybinds.content_right,
+            &c.keybinds.fine_modifier,
+        ) {
+            self.add_volume(c.audio.fine_step, true);
+        } else if c.key_matches(key_event, &c.keybinds.content_left) {
+            self.add_volume(c.audio.volume_step, false);
+        } else if c.key_matches(key_event, &c.keybinds.content_right) {
+            self.add_volume(c.audio.volume_step, true);
+        } else if c.key_matches(key_event, &c.keybinds.accept) {
+            if let Some(AudioRow::Stream(i)) = self.selected() {
+                self.open_move_overlay(i);
+            } else {
+                self.make_selected_default();
+            }
+        } else if c.key_matches(key_event, &c.keybinds.toggle) {
+            self.toggle_selected_mute();
+        } else if c.key_matches(key_event, &c.keybinds.solo) {
+            self.toggle_solo();
+        } else if c.key_matches(key_event, &c.keybinds.profiles) {
+            self.open_profile_picker();
+        } else if c.key_matches(key_event, &c.keybinds.info)
+            && let Some(AudioRow::Stream(i)) = self.selected()
+        {
+            self.info_stream = Some(self.audio_list[i].id.clone());
         }
     }
 
-    /// Build the main and overlay widgets for rendering.
-    pub fn get_widget(&self, area: Rect) -> WiMenuItem<'static> {
-        let max_width = (area.width - 4) as usize;
+    fn handle_move_overlay_key(&mut self, key_event: &KeyEvent) {
+        let c = CONFIG();
+        let sink_count = self.sinks.len();
+        let Some(overlay) = self.move_overlay.as_mut() else {
+            return;
+        };
 
-        let list = self.make_audio_widget_list(max_width);
+        if c.key_matches(key_event, &c.keybinds.content_up) && sink_count > 0 {
+            overlay.selected_sink = (overlay.selected_sink + sink_count - 1) % sink_count;
+        } else if c.key_matches(key_event, &c.keybinds.content_down) && sink_count > 0 {
+            overlay.selected_sink = (overlay.selected_sink + 1) % sink_count;
+        } else if c.key_matches(key_event, &c.keybinds.accept) {
+            self.accept_move();
+        } else if c.key_matches(key_event, &c.keybinds.cancel) {
+            self.move_overlay = None;
+        }
+    }
 
-        let (overlay, overlay_area) = self.make_empty_prompt();
+    fn handle_profile_picker_key(&mut self, key_event: &KeyEvent) {
+        let c = CONFIG();
+        let Some(picker) = self.profile_picker.as_mut() else {
+            return;
+        };
 
-        WiMenuItem {
-            content: EnContentMenuItem::List(list),
-            overlay,
-            overlay_area,
-            show_overlay: false,
+        if let Some(name) = picker.naming.as_mut() {
+            if c.key_matches(key_event, &c.keybinds.accept) {
+                self.save_profile();
+            } else if c.key_matches(key_event, &c.keybinds.cancel) {
+                picker.naming = None;
+            } else if key_event.code == KeyCode::Backspace {
+                name.pop();
+            } else if let KeyCode::Char(ch) = key_event.code {
+                name.push(ch);
+            }
+            return;
         }
-    }
 
-    /// Start background thread to refresh audio list.
-    pub fn start_auto_refresh(this: Arc<Mutex<Self>>) {
-        {
-            let mut guard = this.lock().unwrap();
-            if guard.started_refresh {
-                return;
+        let row_count = picker.profiles.len() + 1;
+        if c.key_matches(key_event, &c.keybinds.content_up) {
+            picker.selected = (picker.selected + row_count - 1) % row_count;
+        } else if c.key_matches(key_event, &c.keybinds.content_down) {
+            picker.selected = (picker.selected + 1) % row_count;
+        } else if c.key_matches(key_event, &c.keybinds.accept) {
+            if picker.selected == 0 {
+                picker.naming = Some(String::new());
+            } else {
+                self.apply_profile();
             }
-            guard.started_refresh = true;
+        } else if c.key_matches(key_event, &c.keybinds.delete) {
+            self.delete_profile();
+        } else if c.key_matches(key_event, &c.keybinds.cancel)
+            || c.key_matches(key_event, &c.keybinds.profiles)
+        {
+            self.profile_picker = None;
+        }
+    }
+
+    fn open_move_overlay(&mut self, stream: usize) {
+        let Some(stream) = self.audio_list.get(stream) else {
+            return;
         };
 
-        std::thread::spawn(move || {
-            loop {
-                let new_list = AudioMixer::make_audio_list();
-
-                if let Ok(mut am) = this.lock() {
-                    am.audio_list = new_list;
-                    if am.audio_list.len() != 0 {
-                        am.selected_volume = am.audio_list[am.selected_audio].1;
-                        am.selected_id = am.audio_list[am.selected_audio].2.clone();
-                    }
-                }
+        let selected_sink = self
+            .sinks
+            .iter()
+            .position(|sink| sink.index == stream.sink)
+            .unwrap_or(0);
 
-                sleep(Duration::from_secs(3));
-            }
+        self.move_overlay = Some(MoveOverlay {
+            stream_id: stream.id.clone(),
+            stream_name: stream.name.clone(),
+            selected_sink,
         });
     }
 
-    // ====== Input Handling ======
-
-    fn handle_key_event(&mut self, key_event: &KeyEvent) {
-        if key_event.kind != KeyEventKind::Press {
+    fn accept_move(&mut self) {
+        let Some(overlay) = self.move_overlay.take() else {
+            return;
+        };
+        let Some(sink) = self.sinks.get(overlay.selected_sink) else {
             return;
+        };
+
+        let sink_name = sink.name.clone();
+        self.send_command(move |backend| backend.move_sink_input(&overlay.stream_id, &sink_name));
+    }
+
+    fn row_count(&self) -> usize {
+        self.sinks.len() + self.sources.len() + self.audio_list.len()
+    }
+
+    fn selected(&self) -> Option<AudioRow> {
+        let mut row = self.selected_row;
+
+        if row < self.sinks.len() {
+            return Some(AudioRow::Sink(row));
         }
+        row -= self.sinks.len();
 
-        let c = CONFIG();
+        if row < self.sources.len() {
+            return Some(AudioRow::Source(row));
+        }
+        row -= self.sources.len();
 
-        if c.key_matches(key_event, &c.keybinds.content_up) {
-            self.move_selected_up();
-        } else if c.key_matches(key_event, &c.keybinds.content_down) {
-            self.move_selected_down();
-        } else if c.key_matches(key_event, &c.keybinds.content_left) {
-            let id = self.selected_id.clone();
-            self.add_volume(&id, 5, false);
-        } else if c.key_matches(key_event, &c.keybinds.content_right) {
-            let id = self.selected_id.clone();
-            self.add_volume(&id, 5, true);
+        (row < self.audio_list.len()).then_some(AudioRow::Stream(row))
+    }
+
+    fn selected_key(&self) -> Option<RowKey> {
+        Some(match self.selected()? {
+            AudioRow::Sink(i) => RowKey::Sink(self.sinks[i].name.clone()),
+            AudioRow::Source(i) => RowKey::Source(self.sources[i].name.clone()),
+            AudioRow::Stream(i) => RowKey::Stream(self.audio_list[i].id.clone()),
+        })
+    }
+
+    fn row_of(&self, key: &RowKey) -> Option<usize> {
+        match key {
+            RowKey::Sink(name) => self.sinks.iter().position(|sink| sink.name == *name),
+            RowKey::Source(name) => self
+                .sources
+                .iter()
+                .position(|source| source.name == *name)
+                .map(|i| self.sinks.len() + i),
+            RowKey::Stream(id) => self
+                .audio_list
+                .iter()
+                .position(|stream| stream.id == *id)
+                .map(|i| self.sinks.len() + self.sources.len() + i),
         }
     }
 
     fn move_selected_down(&mut self) {
-        self.selected_audio += 1;
-        if self.selected_audio >= self.audio_list.len() {
-            self.selected_audio = 0;
+        if self.row_count() == 0 {
+            return;
         }
 
-        let (_, volume, id) = &self.audio_list[self.selected_audio];
-        self.selected_volume = *volume;
-        self.selected_id = id.clone();
+        self.selected_row += 1;
+        if self.selected_row >= self.row_count() {
+            self.selected_row = 0;
+        }
     }
 
     fn move_selected_up(&mut self) {
-        let number = self.selected_audio as i32 - 1;
-        self.selected_audio = self.selected_audio.saturating_sub(1);
+        if self.row_count() == 0 {
+            return;
+        }
+
+        let number = self.selected_row as i32 - 1;
+        self.selected_row = self.selected_row.saturating_sub(1);
 
         if number < 0 {
-            self.selected_audio = self.audio_list.len() - 1;
+            self.selected_row = self.row_count() - 1;
         }
+    }
 
-        let (_, volume, id) = &self.audio_list[self.selected_audio];
-        self.selected_volume = *volume;
-        self.selected_id = id.clone();
+    fn apply_state(&mut self, state: Result<AudioState>) {
+        let selected = self.selected_key();
+
+        match state {
+            Ok(state) => {
+                self.sinks = state.sinks;
+                self.default_sink = state.default_sink;
+                self.sources = state.sources;
+                self.default_source = state.default_source;
+                self.audio_list = state.sink_inputs;
+                self.status.clear();
+            }
+            Err(e) => {
+                self.sinks.clear();
+                self.sources.clear();
+                self.audio_list.clear();
+                self.status = e.to_string();
+            }
+        }
+
+        // follow the selected device or stream to its new row; when it is gone,
+        // keep the position
+        self.selected_row = selected
+            .and_then(|key| self.row_of(&key))
+            .unwrap_or_else(|| self.selected_row.min(self.row_count().saturating_sub(1)));
     }
 
     // ====== Rendering UI Components ======
 
-    fn make_audio_widget_list(&self, max_width: usize) -> List<'static> {
+    /// Lines of the mixer, scrolled so the selected row fits in `height` lines.
+    fn make_audio_widget_list(&self, max_width: usize, height: usize) -> List<'static> {
         let mut items: Vec<Line> = Vec::new();
-        let mut audio_lines: Vec<Line> = Vec::new();
+        let selected = self.selected();
+        // line after the selected row's last one
+        let mut selected_end = 0;
 
-        for (i, (audio, volume, id)) in self.audio_list.iter().enumerate() {
-            let color = if i == self.selected_audio {
-                CONFIG().themes.content_selected_color
-            } else {
-                CONFIG().themes.fg_color
-            };
+        let section = |items: &mut Vec<Line<'static>>, title: &str| {
+            if !items.is_empty() {
+                items.push(Line::from(""));
+            }
+            items.push(self.make_section_line(title));
+        };
+
+        if !self.sinks.is_empty() {
+            section(&mut items, "Outputs");
+        }
 
-            let mut name_line = self.make_audio_name_line(audio);
-            name_line = name_line.style(color);
+        for (i, sink) in self.sinks.iter().enumerate() {
+            let is_selected = matches!(selected, Some(AudioRow::Sink(s)) if s == i);
+            let name_line = self.make_device_name_line(sink, &self.default_sink);
+            items.push(self.style_row(name_line, is_selected));
+            for volume_line in
+                self.make_volume_lines(is_selected, &sink.channels, sink.muted, max_width)
+            {
+                items.push(self.style_row(volume_line, is_selected));
+            }
+            if is_selected {
+                selected_end = items.len();
+            }
+        }
 
-            let mut volume_line = self.make_audio_volume_line(id, volume, max_width);
-            volume_line = volume_line.style(color);
+        if !self.sources.is_empty() {
+            section(&mut items, "Inputs");
+        }
 
-            audio_lines.push(name_line);
-            audio_lines.push(volume_line);
+        for (i, source) in self.sources.iter().enumerate() {
+            let is_selected = matches!(selected, Some(AudioRow::Source(s)) if s == i);
+            let name_line = self.make_device_name_line(source, &self.default_source);
+            items.push(self.style_row(name_line, is_selected));
+            for volume_line in
+                self.make_volume_lines(is_selected, &source.channels, source.muted, max_width)
+            {
+                items.push(self.style_row(volume_line, is_selected));
+            }
+            if is_selected {
+                selected_end = items.len();
+            }
         }
 
-        items.append(&mut audio_lines);
+        if !self.audio_list.is_empty() {
+            section(&mut items, "Applications");
+        }
+
+        for (i, stream) in self.audio_list.iter().enumerate() {
+            let is_selected = matches!(selected, Some(AudioRow::Stream(s)) if s == i);
+            let name_line = self.make_audio_name_line(stream);
+            items.push(self.style_row(name_line, is_selected));
+            for volume_line in
+                self.make_volume_lines(is_selected, &stream.channels, stream.muted, max_width)
+            {
+                items.push(self.style_row(volume_line, is_selected));
+            }
+            if is_selected {
+                selected_end = items.len();
+            }
+        }
+
+        if !self.status.is_empty() {
+            items.push(Line::from(Span::styled(
+                self.status.clone(),
+                Style::default().fg(Color::Red),
+            )));
+        }
+
+        let first_shown = selected_end.saturating_sub(height);
+        let items: Vec<Line> = items.into_iter().skip(first_shown).collect();
 
         let theme = &CONFIG().themes;
 
@@ -183,19 +516,100 @@ impl AudioMixer {
         List::new(items).block(block)
     }
 
-    fn make_audio_name_line(&self, name: &str) -> Line<'static> {
-        let audio_name = format!("♪ {}", name.to_string());
+    fn style_row(&self, line: Line<'static>, is_selected: bool) -> Line<'static> {
+        if is_selected {
+            line.style(CONFIG().themes.content_selected_color)
+        } else {
+            line.style(CONFIG().themes.fg_color)
+        }
+    }
+
+    fn make_section_line(&self, title: &str) -> Line<'static> {
+        Line::from(Span::styled(
+            title.to_string(),
+            Style::default()
+                .fg(CONFIG().themes.border_color)
+                .add_modifier(Modifier::BOLD),
+        ))
+    }
+
+    /// `●` marks the default device.
+    fn make_device_name_line(&self, device: &AudioDevice, default: &str) -> Line<'static> {
+        let marker = if device.name == default { "●" } else { "○" };
+        let muted = if device.muted { " (muted)" } else { "" };
+        Line::from(Span::raw(format!(
+            "{} {}{}",
+            marker, device.description, muted
+        )))
+    }
+
+    fn make_audio_name_line(&self, stream: &SinkInput) -> Line<'static> {
+        let mut audio_name = format!("♪ {}", stream.name);
+        if let Some(media_name) = &stream.media_name
+            && *media_name != stream.name
+        {
+            audio_name.push_str(&format!(" · {}", media_name));
+        }
+        if stream.muted {
+            audio_name.push_str(" (muted)");
+        }
+        if self
+            .solo
+            .as_ref()
+            .is_some_and(|solo| solo.stream_id == stream.id)
+        {
+            audio_name.push_str(" (solo)");
+        }
         Line::from(Span::raw(audio_name))
     }
 
-    fn make_audio_volume_line(&self, id: &String, volume: &u8, max_width: usize) -> Line<'static> {
+    /// One bar for the overall volume, or separate `L` and `R` bars when the
+    /// left and right channels differ.
+    fn make_volume_lines(
+        &self,
+        is_selected: bool,
+        channels: &[ChannelVolume],
+        muted: bool,
+        max_width: usize,
+    ) -> Vec<Line<'static>> {
+        match audio::side_volumes(channels) {
+            Some((left, right)) if left != right => [("L ", left), ("R ", right)]
+                .into_iter()
+                .map(|(label, volume)| {
+                    let mut line = self.make_audio_volume_line(
+                        is_selected,
+                        volume,
+                        muted,
+                        max_width.saturating_sub(label.len()),
+                    );
+                    line.spans.insert(0, Span::raw(label));
+                    line
+                })
+                .collect(),
+            _ => vec![self.make_audio_volume_line(
+                is_selected,
+                audio::overall_volume(channels),
+                muted,
+                max_width,
+            )],
+        }
+    }
+
+    /// Muted rows are drawn with `~` in dark gray instead of the bar colors.
+    fn make_audio_volume_line(
+        &self,
+        is_selected: bool,
+        volume: u8,
+        muted: bool,
+        max_width: usize,
+    ) -> Line<'static> {
         let bar_length = max_width.saturating_sub(2) as u8;
-        let clamped_volume = *volume;
+        // the full bar is 100%, or `max_volume` when over-amplifying is allowed
+        let full_volume = CONFIG().audio.max_volume.max(100) as u32;
         let filled_len =
-            ((clamped_volume as u32 * bar_length as u32) / 100).clamp(0, bar_length as u32) as u8;
+            ((volume as u32 * bar_length as u32) / full_volume).clamp(0, bar_length as u32) as u8;
         let empty_len = bar_length.saturating_sub(filled_len);
 
-        let is_selected = *id == self.selected_id;
         let theme = &CONFIG().themes;
 
         let bar_side_color = if !is_selected {
@@ -213,10 +627,15 @@ impl AudioMixer {
         } else {
             theme.bar_empty_color
         };
+        let (filled_char, filled_color) = if muted {
+            ("~", Color::DarkGray)
+        } else {
+            ("=", filled_color)
+        };
 
         let open_bracket = Span::styled("[", Style::default().fg(bar_side_color));
         let filled = Span::styled(
-            "=".repeat(filled_len as usize),
+            filled_char.repeat(filled_len as usize),
             Style::default().fg(filled_color),
         );
         let empty = Span::styled(
@@ -228,6 +647,157 @@ impl AudioMixer {
         Line::from(vec![open_bracket, filled, empty, close_bracket])
     }
 
+    fn make_move_overlay(
+        &self,
+        overlay: &MoveOverlay,
+        max_width: usize,
+        area: Rect,
+    ) -> (EnContentMenuItem<'static>, Rect) {
+        let theme = &CONFIG().themes;
+
+        let lines: Vec<Line> = self
+            .sinks
+            .iter()
+            .enumerate()
+            .map(|(i, sink)| {
+                let line = self.make_device_name_line(sink, &self.default_sink);
+                if i == overlay.selected_sink {
+                    line.style(theme.content_selected_color)
+                } else {
+                    line.style(theme.fg_color)
+                }
+            })
+            .collect();
+
+        let paragraph = Paragraph::new(lines)
+            .block(overlay_block().title(format!("Move {} to", overlay.stream_name)));
+
+        (
+            EnContentMenuItem::Paragraph(paragraph),
+            overlay_rect(
+                area,
+                max_width.saturating_sub(2) as u16,
+                self.sinks.len() as u16 + 2,
+                3,
+            ),
+        )
+    }
+
+    fn make_info_overlay(
+        &self,
+        stream_id: &str,
+        max_width: usize,
+        area: Rect,
+    ) -> (EnContentMenuItem<'static>, Rect) {
+        let unknown = || "-".to_string();
+
+        let lines: Vec<Line> = match self.audio_list.iter().find(|s| s.id == stream_id) {
+            Some(stream) => {
+                let output = self
+                    .sinks
+                    .iter()
+                    .find(|sink| sink.index == stream.sink)
+                    .map(|sink| sink.description.clone())
+                    .unwrap_or_else(unknown);
+                let channels = stream
+                    .channels
+                    .iter()
+                    .map(|c| format!("{} {}%", c.channel, c.percent))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                [
+                    ("Application", stream.name.clone()),
+                    ("Media", stream.media_name.clone().unwrap_or_else(unknown)),
+                    ("Binary", stream.binary.clone().unwrap_or_else(unknown)),
+                    (
+                        "PID",
+                        stream
+                            .pid
+                            .map(|pid| pid.to_string())
+                            .unwrap_or_else(unknown),
+                    ),
+                    ("Output", output),
+                    ("Channels", channels),
+                    (
+                        "Balance",
+                        Self::format_balance(audio::balance(&stream.channels)),
+                    ),
+                    ("Muted", if stream.muted { "yes" } else { "no" }.to_string()),
+                ]
+                .into_iter()
+                .map(|(label, value)| {
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{:<12}", label),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                        Span::raw(value),
+                    ])
+                })
+                .collect()
+            }
+            None => vec![Line::from("The stream has ended.")],
+        };
+
+        let h = lines.len() as u16 + 2;
+        let paragraph = Paragraph::new(lines).block(overlay_block().title("Stream"));
+
+        (
+            EnContentMenuItem::Paragraph(paragraph),
+            overlay_rect(area, max_width.saturating_sub(2) as u16, h, 3),
+        )
+    }
+
+    /// `center`, `L 20` or `R 20`.
+    fn format_balance(balance: i32) -> String {
+        match balance {
+            0 => "center".to_string(),
+            b if b < 0 => format!("L {}", -b),
+            b => format!("R {}", b),
+        }
+    }
+
+    fn make_profile_overlay(
+        &self,
+        picker: &ProfilePicker,
+        max_width: usize,
+        area: Rect,
+    ) -> (EnContentMenuItem<'static>, Rect) {
+        let theme = &CONFIG().themes;
+
+        let (title, lines): (&str, Vec<Line>) = match &picker.naming {
+            Some(name) => ("Save profile as", vec![Line::from(format!("{}█", name))]),
+            None => {
+                let rows = std::iter::once("+ Save current volumes".to_string()).chain(
+                    picker.profiles.iter().map(|profile| {
+                        format!("{} ({} apps)", profile.name, profile.streams.len())
+                    }),
+                );
+                let lines = rows
+                    .enumerate()
+                    .map(|(i, row)| {
+                        let line = Line::from(row);
+                        if i == picker.selected {
+                            line.style(theme.content_selected_color)
+                        } else {
+                            line.style(theme.fg_color)
+                        }
+                    })
+                    .collect();
+                ("Profiles", lines)
+            }
+        };
+
+        let h = lines.len() as u16 + 2;
+        let paragraph = Paragraph::new(lines).block(overlay_block().title(title));
+
+        (
+            EnContentMenuItem::Paragraph(paragraph),
+            overlay_rect(area, max_width.saturating_sub(2) as u16, h, 3),
+        )
+    }
+
     fn make_empty_prompt(&self) -> (EnContentMenuItem<'static>, Rect) {
         (
             EnContentMenuItem::Paragraph(
@@ -239,71 +809,548 @@ impl AudioMixer {
 
     // ====== audio-related Commands ======
 
-    pub fn add_volume(&mut self, id: &String, amount: u8, increase: bool) {
-        let mut am = amount;
-        let vol: i32 = self.selected_volume as i32;
-        if vol + (amount as i32) > 100 && increase {
-            am = 0;
+    /// Queue a command for the refresh thread, which runs it and reads the
+    /// state again; `tick` applies that state, or shows why the command failed.
+    fn send_command(&self, command: impl FnOnce(&dyn AudioBackend) -> Result<()> + Send + 'static) {
+        let _ = self
+            .message_tx
+            .send(RefreshMessage::Command(Box::new(command)));
+    }
+
+    fn selected_channels(&self) -> Option<&[ChannelVolume]> {
+        match self.selected()? {
+            AudioRow::Sink(i) => Some(&self.sinks[i].channels),
+            AudioRow::Source(i) => Some(&self.sources[i].channels),
+            AudioRow::Stream(i) => Some(&self.audio_list[i].channels),
         }
-        if vol - (amount as i32) < 0 && !increase {
-            am = 0;
+    }
+
+    fn selected_channels_mut(&mut self) -> Option<&mut Vec<ChannelVolume>> {
+        match self.selected()? {
+            AudioRow::Sink(i) => Some(&mut self.sinks[i].channels),
+            AudioRow::Source(i) => Some(&mut self.sources[i].channels),
+            AudioRow::Stream(i) => Some(&mut self.audio_list[i].channels),
         }
+    }
 
-        let volume_change = format!("{}{}%", if increase { "+" } else { "-" }, am);
+    /// Set the channel volumes of the selected row. They are shown right away,
+    /// so a held key keeps stepping from the new volume before the refresh.
+    fn set_selected_volumes(&mut self, volumes: Vec<u8>) {
+        match self.selected() {
+            Some(AudioRow::Sink(i)) => {
+                let name = self.sinks[i].name.clone();
+                let sent = volumes.clone();
+                self.send_command(move |backend| backend.set_sink_volume(&name, &sent));
+            }
+            Some(AudioRow::Source(i)) => {
+                let name = self.sources[i].name.clone();
+                let sent = volumes.clone();
+                self.send_command(move |backend| backend.set_source_volume(&name, &sent));
+            }
+            Some(AudioRow::Stream(i)) => {
+                let id = self.audio_list[i].id.clone();
+                let sent = volumes.clone();
+                self.send_command(move |backend| backend.set_sink_input_volume(&id, &sent));
+            }
+            None => return,
+        }
 
-        let status = Command::new("pactl")
-            .args(["set-sink-input-volume", id, &volume_change])
-            .status();
+        if let Some(channels) = self.selected_channels_mut() {
+            for (channel, percent) in channels.iter_mut().zip(volumes) {
+                channel.percent = percent;
+            }
+        }
+    }
+
+    /// Step the overall volume of the selected row, keeping its balance.
+    fn add_volume(&mut self, amount: u8, increase: bool) {
+        let Some(channels) = self.selected_channels() else {
+            return;
+        };
+
+        let volume = Self::next_volume(
+            audio::overall_volume(channels),
+            amount,
+            increase,
+            CONFIG().audio.max_volume,
+        );
+        let volumes = audio::scale_channels(channels, volume);
+        self.set_selected_volumes(volumes);
+    }
+
+    /// Shift the balance of the selected row; negative amounts lean left.
+    fn add_balance(&mut self, amount: i32) {
+        let Some(channels) = self.selected_channels() else {
+            return;
+        };
+        if audio::side_volumes(channels).is_none() {
+            return;
+        }
+
+        let volumes = audio::balance_channels(channels, audio::balance(channels) + amount);
+        self.set_selected_volumes(volumes);
+    }
+
+    /// Make the selected output or input the default one.
+    fn make_selected_default(&mut self) {
+        match self.selected() {
+            Some(AudioRow::Sink(i)) => {
+                let name = self.sinks[i].name.clone();
+                self.send_command(move |backend| backend.set_default_sink(&name));
+            }
+            Some(AudioRow::Source(i)) => {
+                let name = self.sources[i].name.clone();
+                self.send_command(move |backend| backend.set_default_source(&name));
+            }
+            _ => {}
+        }
+    }
+
+    /// Toggle the mute of the selected row, shown right away like volumes.
+    fn toggle_selected_mute(&mut self) {
+        match self.selected() {
+            Some(AudioRow::Sink(i)) => {
+                let sink = &mut self.sinks[i];
+                sink.muted = !sink.muted;
+                let (name, muted) = (sink.name.clone(), sink.muted);
+                self.send_command(move |backend| backend.set_sink_mute(&name, muted));
+            }
+            Some(AudioRow::Source(i)) => {
+                let source = &mut self.sources[i];
+                source.muted = !source.muted;
+                let (name, muted) = (source.name.clone(), source.muted);
+                self.send_command(move |backend| backend.set_source_mute(&name, muted));
+            }
+            Some(AudioRow::Stream(i)) => {
+                self.set_stream_mutes(vec![(
+                    self.audio_list[i].id.clone(),
+                    !self.audio_list[i].muted,
+                )]);
+            }
+            None => {}
+        }
+    }
 
-        match status {
-            Ok(s) if s.success() => {
-                self.audio_list = AudioMixer::make_audio_list();
+    /// Set the mute state of several streams in one command.
+    fn set_stream_mutes(&mut self, mutes: Vec<(String, bool)>) {
+        for stream in self.audio_list.iter_mut() {
+            if let Some((_, muted)) = mutes.iter().find(|(id, _)| *id == stream.id) {
+                stream.muted = *muted;
             }
-            Ok(s) => {}
-            Err(e) => {}
         }
+
+        self.send_command(move |backend| {
+            mutes
+                .iter()
+                .try_for_each(|(id, muted)| backend.set_sink_input_mute(id, *muted))
+        });
     }
 
-    fn make_audio_list() -> Vec<(String, u8, String)> {
-        let output = Command::new("pactl")
-            .arg("list")
-            .arg("sink-inputs")
-            .output()
-            .expect("Failed to run pactl");
+    /// Solo the selected stream by muting all others, or end the current solo
+    /// and restore the mute states from before it.
+    fn toggle_solo(&mut self) {
+        if let Some(solo) = self.solo.take() {
+            let mutes = solo
+                .saved_mutes
+                .into_iter()
+                .filter(|(id, _)| self.audio_list.iter().any(|stream| stream.id == *id))
+                .collect();
+            self.set_stream_mutes(mutes);
+            return;
+        }
+
+        let Some(AudioRow::Stream(i)) = self.selected() else {
+            return;
+        };
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut result = Vec::new();
+        let stream_id = self.audio_list[i].id.clone();
+        let saved_mutes = self
+            .audio_list
+            .iter()
+            .map(|stream| (stream.id.clone(), stream.muted))
+            .collect();
+        let mutes = self
+            .audio_list
+            .iter()
+            .map(|stream| (stream.id.clone(), stream.id != stream_id))
+            .collect();
 
-        let mut current_id = String::new();
-        let mut current_name = String::new();
-        let mut current_volume = 0;
+        self.solo = Some(Solo {
+            stream_id,
+            saved_mutes,
+        });
+        self.set_stream_mutes(mutes);
+    }
 
-        for line in stdout.lines() {
-            if line.trim_start().starts_with("Sink Input") {
-                if let Some(id) = line.split('#').nth(1) {
-                    current_id = id.to_string();
-                }
+    // ====== Profiles ======
+
+    fn open_profile_picker(&mut self) {
+        match audio_profiles::load() {
+            Ok(profiles) => {
+                self.profile_picker = Some(ProfilePicker {
+                    profiles,
+                    selected: 0,
+                    naming: None,
+                })
             }
+            Err(e) => self.status = e.to_string(),
+        }
+    }
+
+    /// Save the current application volumes under the typed name, replacing
+    /// a profile with the same name.
+    fn save_profile(&mut self) {
+        let Some(picker) = self.profile_picker.as_mut() else {
+            return;
+        };
+        let name = picker.naming.as_deref().unwrap_or("").trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+
+        let profile = AudioProfile::capture(&name, &self.audio_list);
+        match picker.profiles.iter_mut().find(|p| p.name == name) {
+            Some(existing) => *existing = profile,
+            None => picker.profiles.push(profile),
+        }
+
+        self.status = match audio_profiles::save(&picker.profiles) {
+            Ok(()) => String::new(),
+            Err(e) => format!("Failed to save profiles: {}", e),
+        };
+        self.profile_picker = None;
+    }
+
+    fn apply_profile(&mut self) {
+        let Some(picker) = self.profile_picker.take() else {
+            return;
+        };
+        let Some(profile) = picker
+            .selected
+            .checked_sub(1)
+            .map(|i| picker.profiles[i].clone())
+        else {
+            return;
+        };
+
+        let max_volume = CONFIG().audio.max_volume;
+        self.send_command(move |backend| profile.apply(backend, max_volume).map(|_| ()));
+    }
+
+    fn delete_profile(&mut self) {
+        let Some(picker) = self.profile_picker.as_mut() else {
+            return;
+        };
+        let Some(i) = picker.selected.checked_sub(1) else {
+            return;
+        };
+
+        picker.profiles.remove(i);
+        picker.selected = picker.selected.min(picker.profiles.len());
+        if let Err(e) = audio_profiles::save(&picker.profiles) {
+            self.status = format!("Failed to save profiles: {}", e);
+        }
+    }
+
+    /// Runs `subscribe` on its own thread, sending `Changed` per change and
+    /// `Unsubscribed` once the subscription ends or is not supported.
+    fn subscribe_events(backend: &Arc<dyn AudioBackend>, message_tx: &Sender<RefreshMessage>) {
+        let backend = backend.clone();
+        let message_tx = message_tx.clone();
+
+        std::thread::spawn(move || {
+            let _ = backend.subscribe(&mut || message_tx.send(RefreshMessage::Changed).is_ok());
+            let _ = message_tx.send(RefreshMessage::Unsubscribed);
+        });
+    }
 
-            if line.trim_start().starts_with("Volume:") {
-                if let Some(percent) = line.split('/').nth(1) {
-                    let volume = percent
-                        .trim()
-                        .trim_end_matches('%')
-                        .parse::<u8>()
-                        .unwrap_or(0);
-                    current_volume = volume;
+    /// Body of the refresh thread: reads the state on start, after change
+    /// events and after commands, or every `POLL_INTERVAL` while events are
+    /// unavailable. Ends at the first wake-up after the mixer is dropped,
+    /// which is at most `POLL_INTERVAL` later; a running subscription ends
+    /// with the next change event.
+    fn refresh_loop(
+        backend: Arc<dyn AudioBackend>,
+        update_tx: Sender<AudioUpdate>,
+        message_tx: Weak<Sender<RefreshMessage>>,
+        message_rx: Receiver<RefreshMessage>,
+    ) {
+        let subscribe = || {
+            if let Some(message_tx) = message_tx.upgrade() {
+                AudioMixer::subscribe_events(&backend, &message_tx);
+            }
+        };
+
+        subscribe();
+        let mut resubscribe_at: Option<Instant> = None;
+        let mut refresh = true;
+
+        loop {
+            // subscriptions hold their own sender, so the channel alone does
+            // not tell whether the mixer is still there
+            if message_tx.strong_count() == 0 {
+                break;
+            }
+
+            if refresh
+                && update_tx
+                    .send(AudioUpdate::State(AudioMixer::read_state(backend.as_ref())))
+                    .is_err()
+            {
+                break;
+            }
+
+            let timeout = resubscribe_at.map_or(POLL_INTERVAL, |at| {
+                at.saturating_duration_since(Instant::now())
+            });
+
+            let first = match message_rx.recv_timeout(timeout) {
+                Ok(message) => message,
+                Err(RecvTimeoutError::Timeout) => {
+                    // poll while there are no events, trying to subscribe again
+                    refresh = resubscribe_at.take().is_some();
+                    if refresh {
+                        subscribe();
+                    }
+                    continue;
                 }
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+
+            if matches!(first, RefreshMessage::Changed) {
+                sleep(EVENT_DEBOUNCE);
             }
 
-            if line.trim_start().starts_with("application.name =") {
-                if let Some(name) = line.split('=').nth(1) {
-                    current_name = name.trim().trim_matches('"').to_string();
-                    result.push((current_name.clone(), current_volume, current_id.clone()));
+            // everything queued meanwhile is handled before a single refresh,
+            // so a held volume key does not read the state once per step
+            let pending: Vec<RefreshMessage> = message_rx.try_iter().collect();
+            refresh = false;
+
+            for message in std::iter::once(first).chain(pending) {
+                match message {
+                    RefreshMessage::Changed => refresh = true,
+                    RefreshMessage::Unsubscribed => {
+                        resubscribe_at = Some(Instant::now() + POLL_INTERVAL)
+                    }
+                    RefreshMessage::Command(command) => match command(backend.as_ref()) {
+                        Ok(()) => refresh = true,
+                        Err(e) => {
+                            let _ = update_tx.send(AudioUpdate::CommandFailed(e.to_string()));
+                        }
+                    },
                 }
             }
         }
+    }
+
+    fn read_state(backend: &dyn AudioBackend) -> Result<AudioState> {
+        Ok(AudioState {
+            sinks: backend.sinks()?,
+            default_sink: backend.default_sink()?,
+            sources: backend.sources()?,
+            default_source: backend.default_source()?,
+            sink_inputs: backend.sink_inputs()?,
+        })
+    }
+
+    /// Volume after one step, clamped to 0..=`max_volume`. A volume already
+    /// above the limit (set by another mixer) is not raised any further.
+    fn next_volume(volume: u8, amount: u8, increase: bool, max_volume: u8) -> u8 {
+        if increase {
+            volume.saturating_add(amount).min(max_volume.max(volume))
+        } else {
+            volume.saturating_sub(amount)
+        }
+    }
+}
+
+impl MenuWidget for AudioMixer {
+    fn title(&self) -> &str {
+        "Audio"
+    }
+
+    fn handle_event(&mut self, event: &Event) -> Result<()> {
+        match event {
+            Event::Key(key_event) => {
+                self.handle_key_event(key_event);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Start background thread to refresh devices and streams whenever the
+    /// backend reports a change, polling instead while events are unavailable,
+    /// and to run the commands of key presses.
+    fn start(&mut self) -> Result<()> {
+        let Some((update_tx, message_rx)) = self.refresh_channels.take() else {
+            return Ok(());
+        };
+
+        let backend = self.backend.clone();
+        let message_tx = Arc::downgrade(&self.message_tx);
+        std::thread::spawn(move || {
+            AudioMixer::refresh_loop(backend, update_tx, message_tx, message_rx)
+        });
+
+        Ok(())
+    }
+
+    /// Build the main and overlay widgets for rendering.
+    fn render(&self, area: Rect) -> WiMenuItem<'static> {
+        let max_width = area.width.saturating_sub(4) as usize;
+        let border_lines = if CONFIG().themes.borders_on { 2 } else { 0 };
+        let height = area.height.saturating_sub(border_lines) as usize;
+
+        let list = self.make_audio_widget_list(max_width, height);
+
+        let (overlay, overlay_area) = if let Some(move_overlay) = &self.move_overlay {
+            self.make_move_overlay(move_overlay, max_width, area)
+        } else if let Some(picker) = &self.profile_picker {
+            self.make_profile_overlay(picker, max_width, area)
+        } else if let Some(stream_id) = &self.info_stream {
+            self.make_info_overlay(stream_id, max_width, area)
+        } else {
+            self.make_empty_prompt()
+        };
+
+        WiMenuItem {
+            content: EnContentMenuItem::List(list),
+            overlay,
+            overlay_area,
+            show_overlay: self.move_overlay.is_some()
+                || self.profile_picker.is_some()
+                || self.info_stream.is_some(),
+        }
+    }
+
+    /// Apply the states and command failures sent by the refresh thread.
+    fn tick(&mut self) {
+        let updates: Vec<AudioUpdate> = self.update_rx.try_iter().collect();
+
+        for update in updates {
+            match update {
+                AudioUpdate::State(state) => self.apply_state(state),
+                AudioUpdate::CommandFailed(error) => self.status = error,
+            }
+        }
+    }
+
+    /// While a profile name is typed, every key goes to the prompt.
+    fn captures_input(&self) -> bool {
+        self.profile_picker
+            .as_ref()
+            .is_some_and(|picker| picker.naming.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::audio::{AudioBackend, FakeAudioBackend};
+    use crate::config::init_test_config;
+    use color_eyre::eyre::eyre;
+
+    #[test]
+    fn next_volume_clamps_to_max_volume() {
+        assert_eq!(AudioMixer::next_volume(98, 5, true, 100), 100);
+        assert_eq!(AudioMixer::next_volume(98, 5, true, 150), 103);
+        assert_eq!(AudioMixer::next_volume(3, 5, false, 100), 0);
+        assert_eq!(AudioMixer::next_volume(255, 5, true, 150), 255);
+    }
+
+    #[test]
+    fn next_volume_does_not_raise_a_volume_above_the_limit() {
+        assert_eq!(AudioMixer::next_volume(120, 5, true, 100), 120);
+        assert_eq!(AudioMixer::next_volume(120, 5, false, 100), 115);
+    }
+
+    #[test]
+    fn volume_step_through_fake_backend_keeps_balance_and_clamps() {
+        let backend = FakeAudioBackend::default();
+        backend.set_sink_input_volume("1", &[98, 49]).unwrap();
+
+        let channels = backend.sink_inputs().unwrap()[0].channels.clone();
+        let volume = AudioMixer::next_volume(audio::overall_volume(&channels), 5, true, 100);
+        let volumes = audio::scale_channels(&channels, volume);
+        backend.set_sink_input_volume("1", &volumes).unwrap();
+
+        let channels = &backend.sink_inputs().unwrap()[0].channels;
+        assert_eq!(audio::side_volumes(channels), Some((100, 50)));
+    }
+
+    fn stream(id: &str) -> SinkInput {
+        SinkInput {
+            id: id.to_string(),
+            name: format!("Stream {}", id),
+            media_name: None,
+            binary: None,
+            pid: None,
+            sink: "0".to_string(),
+            channels: Vec::new(),
+            muted: false,
+        }
+    }
+
+    fn state(stream_ids: &[&str]) -> AudioState {
+        AudioState {
+            sinks: Vec::new(),
+            default_sink: String::new(),
+            sources: Vec::new(),
+            default_source: String::new(),
+            sink_inputs: stream_ids.iter().map(|id| stream(id)).collect(),
+        }
+    }
+
+    #[test]
+    fn selection_follows_the_stream_across_refreshes() {
+        let mut mixer = AudioMixer::with_backend(Arc::new(FakeAudioBackend::default()));
+        mixer.apply_state(Ok(state(&["10", "11", "12"])));
+        mixer.move_selected_down();
+
+        // a new stream appears above the selected one
+        mixer.apply_state(Ok(state(&["9", "10", "11", "12"])));
+        assert_eq!(mixer.selected_key(), Some(RowKey::Stream("11".into())));
+
+        // the selected stream goes away: the position is kept
+        mixer.apply_state(Ok(state(&["9", "10", "12"])));
+        assert_eq!(mixer.selected_key(), Some(RowKey::Stream("12".into())));
+
+        mixer.apply_state(Ok(state(&["9"])));
+        assert_eq!(mixer.selected_key(), Some(RowKey::Stream("9".into())));
+    }
+
+    /// Tick until the refresh thread has sent its update.
+    fn tick_until(mixer: &mut AudioMixer, done: impl Fn(&AudioMixer) -> bool) {
+        for _ in 0..100 {
+            mixer.tick();
+            if done(mixer) {
+                return;
+            }
+            sleep(Duration::from_millis(10));
+        }
+        panic!("no update arrived");
+    }
+
+    #[test]
+    fn commands_run_on_the_refresh_thread_and_report_back() {
+        init_test_config();
+        let backend = Arc::new(FakeAudioBackend::default());
+        let mut mixer = AudioMixer::with_backend(backend.clone());
+        mixer.start().unwrap();
+        tick_until(&mut mixer, |mixer| !mixer.sinks.is_empty());
+
+        // the second step starts from the first one before any refresh
+        mixer.add_volume(5, true);
+        mixer.add_volume(5, true);
+        assert_eq!(audio::overall_volume(&mixer.sinks[0].channels), 80);
+        tick_until(&mut mixer, |_| {
+            audio::overall_volume(&backend.sinks().unwrap()[0].channels) == 80
+        });
 
-        result
+        mixer.send_command(|_| Err(eyre!("pactl did not answer")));
+        tick_until(&mut mixer, |mixer| !mixer.status.is_empty());
+        assert_eq!(mixer.status, "pactl did not answer");
+        assert!(!mixer.sinks.is_empty());
     }
 }
@@ -0,0 +1,1062 @@
+use chrono::{
+    Datelike, Days, Duration, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use color_eyre::Result;
+use crossterm::event::{Event, KeyEvent, KeyEventKind};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Padding, Paragraph},
+};
+use std::{fs, path::PathBuf, time::SystemTime};
+
+use crate::{
+    config::{CONFIG, aex_config_dir},
+    widgets::content_menu::{
+        EnContentMenuItem, MenuWidget, WiMenuItem, overlay_block, overlay_rect,
+    },
+};
+
+const WEEKDAY_HEADER: &str = "Mo  Tu  We  Th  Fr  Sa  Su ";
+const OVERLAY_HEIGHT: usize = 12;
+/// How far past today recurring events are expanded.
+const RECURRENCE_YEARS: i64 = 5;
+
+/// One VEVENT from the `.ics` file, converted to local time.
+#[derive(Clone)]
+struct CalendarEvent {
+    summary: String,
+    location: String,
+    start: NaiveDateTime,
+    /// last day the event covers (inclusive)
+    end_date: NaiveDate,
+    all_day: bool,
+}
+
+/// Calendar panel: month grid with today highlighted and events from a local `.ics` file.
+#[derive(Clone)]
+pub struct Calendar {
+    cursor: NaiveDate,
+    events: Vec<CalendarEvent>,
+    events_modified: Option<SystemTime>,
+    show_day: bool,
+    scroll_offset: usize,
+    status: String,
+}
+
+impl Calendar {
+    // ====== Initialization ======
+
+    pub fn new() -> Self {
+        let mut calendar = Self {
+            cursor: Local::now().date_naive(),
+            events: Vec::new(),
+            events_modified: None,
+            show_day: false,
+            scroll_offset: 0,
+            status: String::new(),
+        };
+        calendar.refresh_events();
+        calendar
+    }
+
+    // ====== Input Handling ======
+
+    fn handle_key_event(&mut self, key_event: &KeyEvent) {
+        if key_event.kind != KeyEventKind::Press {
+            return;
+        }
+
+        let c = CONFIG();
+
+        if self.show_day {
+            if c.key_matches(key_event, &c.keybinds.content_up) {
+                self.scroll_offset = self.scroll_offset.saturating_sub(1);
+            } else if c.key_matches(key_event, &c.keybinds.content_down) {
+                let max_offset = self.day_lines().len().saturating_sub(OVERLAY_HEIGHT - 2);
+                self.scroll_offset = (self.scroll_offset + 1).min(max_offset);
+            } else if c.key_matches(key_event, &c.keybinds.accept)
+                || c.key_matches(key_event, &c.keybinds.cancel)
+                || c.key_matches(key_event, &c.keybinds.info)
+            {
+                self.show_day = false;
+                self.scroll_offset = 0;
+            }
+
+            return;
+        }
+
+        if c.key_matches(key_event, &c.keybinds.content_left) {
+            self.move_cursor(-1);
+        } else if c.key_matches(key_event, &c.keybinds.content_right) {
+            self.move_cursor(1);
+        } else if c.key_matches(key_event, &c.keybinds.content_up) {
+            self.move_cursor(-7);
+        } else if c.key_matches(key_event, &c.keybinds.content_down) {
+            self.move_cursor(7);
+        } else if c.key_matches(key_event, &c.keybinds.accept) {
+            self.refresh_events();
+            self.show_day = true;
+        } else if c.key_matches(key_event, &c.keybinds.info) {
+            self.cursor = Local::now().date_naive();
+        }
+    }
+
+    fn move_cursor(&mut self, days: i64) {
+        self.cursor += Duration::days(days);
+    }
+
+    // ====== Rendering UI Components ======
+
+    fn make_month_paragraph(&self) -> Paragraph<'static> {
+        let theme = &CONFIG().themes;
+        let today = Local::now().date_naive();
+
+        let first = self.cursor.with_day(1).unwrap_or(self.cursor);
+        let leading_days = first.weekday().num_days_from_monday() as i64;
+        let mut day = first - Duration::days(leading_days);
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                self.cursor.format("%B %Y").to_string(),
+                Style::default()
+                    .fg(theme.content_selected_color)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                WEEKDAY_HEADER,
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+
+        loop {
+            let mut spans = Vec::new();
+            for weekday in 0..7 {
+                if weekday > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(self.make_day_span(day, today, first.month()));
+                day += Duration::days(1);
+            }
+            lines.push(Line::from(spans));
+
+            if day.month() != first.month() {
+                break;
+            }
+        }
+
+        lines.push(Line::from(""));
+        let count = self.events_on(self.cursor).len();
+        lines.push(Line::from(format!(
+            "{} · {} event{}",
+            self.cursor.format("%a %d %b"),
+            count,
+            if count == 1 { "" } else { "s" }
+        )));
+
+        if !self.status.is_empty() {
+            lines.push(Line::from(Span::styled(
+                self.status.clone(),
+                Style::default().fg(Color::Red),
+            )));
+        }
+
+        let borders = if theme.borders_on {
+            Borders::ALL
+        } else {
+            Borders::NONE
+        };
+
+        Paragraph::new(lines)
+            .style(Style::default().fg(theme.fg_color))
+            .centered()
+            .block(
+                Block::default()
+                    .borders(borders)
+                    .border_type(theme.border_type)
+                    .border_style(Style::default().fg(theme.border_color))
+                    .padding(Padding {
+                        left: 1,
+                        right: 1,
+                        top: 0,
+                        bottom: 0,
+                    }),
+            )
+    }
+
+    /// Three columns: the day number and an event marker.
+    fn make_day_span(&self, day: NaiveDate, today: NaiveDate, month: u32) -> Span<'static> {
+        let theme = &CONFIG().themes;
+
+        let marker = if self.events_on(day).is_empty() {
+            " "
+        } else {
+            "•"
+        };
+        let text = format!("{:>2}{}", day.day(), marker);
+
+        let mut style = if day.month() != month {
+            Style::default().fg(Color::DarkGray)
+        } else {
+            Style::default().fg(theme.fg_color)
+        };
+
+        if day == today {
+            style = style
+                .fg(theme.content_selected_color)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        }
+        if day == self.cursor {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+
+        Span::styled(text, style)
+    }
+
+    fn make_day_overlay(&self, max_width: usize, area: Rect) -> (EnContentMenuItem<'static>, Rect) {
+        let theme = &CONFIG().themes;
+        let content_height = OVERLAY_HEIGHT - 2;
+        let day_lines = self.day_lines();
+
+        let lines: Vec<Line> = day_lines
+            .iter()
+            .skip(self.scroll_offset)
+            .take(content_height)
+            .map(|(text, is_title)| {
+                let style = if *is_title {
+                    Style::default()
+                        .fg(theme.fg_color)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                Line::from(Span::styled(text.clone(), style))
+            })
+            .collect();
+
+        let paragraph = Paragraph::new(lines)
+            .block(overlay_block().title(self.cursor.format("%A, %d %B %Y").to_string()));
+
+        (
+            EnContentMenuItem::Paragraph(paragraph),
+            overlay_rect(area, (max_width + 4) as u16, OVERLAY_HEIGHT as u16, 0),
+        )
+    }
+
+    /// Lines of the day overlay, flagged `true` for event titles.
+    fn day_lines(&self) -> Vec<(String, bool)> {
+        let events = self.events_on(self.cursor);
+        if events.is_empty() {
+            return vec![("No events".to_string(), false)];
+        }
+
+        let mut lines = Vec::new();
+        for event in events {
+            let time = if event.all_day {
+                "all day".to_string()
+            } else if event.start.date() != self.cursor {
+                "cont.".to_string()
+            } else {
+                event.start.format("%H:%M").to_string()
+            };
+            lines.push((format!("{:<8}{}", time, event.summary), true));
+            if !event.location.is_empty() {
+                lines.push((format!("{:<8}{}", "", event.location), false));
+            }
+        }
+        lines
+    }
+
+    fn make_empty_prompt(&self) -> (EnContentMenuItem<'static>, Rect) {
+        (
+            EnContentMenuItem::Paragraph(
+                Paragraph::new("").style(Style::default().bg(CONFIG().themes.bg_color)),
+            ),
+            Rect::default(),
+        )
+    }
+
+    // ====== Events File ======
+
+    fn events_on(&self, day: NaiveDate) -> Vec<&CalendarEvent> {
+        let mut events: Vec<&CalendarEvent> = self
+            .events
+            .iter()
+            .filter(|e| e.start.date() <= day && day <= e.end_date)
+            .collect();
+        events.sort_by_key(|e| (!e.all_day, e.start));
+        events
+    }
+
+    fn events_path() -> Option<PathBuf> {
+        let file = CONFIG().calendar.ics_file.as_ref()?;
+        let path = PathBuf::from(file);
+        if path.is_absolute() {
+            return Some(path);
+        }
+
+        let mut config_path = aex_config_dir().ok()?;
+        config_path.push(path);
+        Some(config_path)
+    }
+
+    /// Re-reads the `.ics` file if it changed since the last read.
+    fn refresh_events(&mut self) {
+        let Some(path) = Self::events_path() else {
+            return;
+        };
+
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+        if modified.is_some() && modified == self.events_modified {
+            return;
+        }
+        self.events_modified = modified;
+
+        match fs::read_to_string(&path) {
+            Ok(ics) => {
+                let horizon = Local::now().date_naive() + Duration::days(366 * RECURRENCE_YEARS);
+                self.events = parse_ics(&ics, horizon);
+                self.status.clear();
+            }
+            Err(e) => {
+                self.events.clear();
+                self.status = format!("Failed to read {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+impl MenuWidget for Calendar {
+    fn title(&self) -> &str {
+        "Calendar"
+    }
+
+    /// Handle input events (keys only).
+    fn handle_event(&mut self, event: &Event) -> Result<()> {
+        match event {
+            Event::Key(key_event) => {
+                self.handle_key_event(key_event);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Build the main and overlay widgets for rendering.
+    fn render(&self, area: Rect) -> WiMenuItem<'static> {
+        let max_width = area.width.saturating_sub(4) as usize;
+
+        let paragraph = self.make_month_paragraph();
+
+        let (overlay, overlay_area) = if self.show_day {
+            self.make_day_overlay(max_width, area)
+        } else {
+            self.make_empty_prompt()
+        };
+
+        WiMenuItem {
+            content: EnContentMenuItem::Paragraph(paragraph),
+            overlay,
+            overlay_area,
+            show_overlay: self.show_day,
+        }
+    }
+
+    /// Pick up changes to the `.ics` file, so the day markers stay current.
+    fn tick(&mut self) {
+        self.refresh_events();
+    }
+}
+
+// ====== ICS Parsing ======
+
+/// Parses the VEVENTs of an iCalendar file. Recurring events are expanded
+/// into one event per occurrence, up to `horizon`.
+fn parse_ics(ics: &str, horizon: NaiveDate) -> Vec<CalendarEvent> {
+    // lines starting with a space or tab continue the previous one
+    let mut lines: Vec<String> = Vec::new();
+    for raw_line in ics.lines() {
+        let raw_line = raw_line.trim_end_matches('\r');
+        if let Some(rest) = raw_line.strip_prefix([' ', '\t'])
+            && let Some(last) = lines.last_mut()
+        {
+            last.push_str(rest);
+        } else {
+            lines.push(raw_line.to_string());
+        }
+    }
+
+    let mut vevents = Vec::new();
+    let mut properties: Option<Vec<Property>> = None;
+
+    for line in lines {
+        if line == "BEGIN:VEVENT" {
+            properties = Some(Vec::new());
+            continue;
+        }
+
+        if line == "END:VEVENT" {
+            vevents.extend(properties.take());
+            continue;
+        }
+
+        let Some(properties) = properties.as_mut() else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (name, params) = key.split_once(';').unwrap_or((key, ""));
+        properties.push((name.to_uppercase(), params.to_string(), value.to_string()));
+    }
+
+    // a VEVENT with a RECURRENCE-ID replaces one occurrence of the event with its UID
+    let overrides: Vec<(&str, NaiveDate)> = vevents
+        .iter()
+        .filter_map(|p| {
+            let (_, _, uid) = find_property(p, "UID")?;
+            let (_, params, value) = find_property(p, "RECURRENCE-ID")?;
+            let (date, _) = parse_ics_datetime(params, value)?;
+            Some((uid.as_str(), date.date()))
+        })
+        .collect();
+
+    let mut events = Vec::new();
+    for p in &vevents {
+        let Some(event) = make_event(p) else {
+            continue;
+        };
+
+        let rule = find_property(p, "RRULE").and_then(|(_, _, v)| RecurrenceRule::parse(v));
+        let Some(rule) = rule.filter(|_| find_property(p, "RECURRENCE-ID").is_none()) else {
+            events.push(event);
+            continue;
+        };
+
+        let uid = find_property(p, "UID").map(|(_, _, v)| v.as_str());
+        let mut exdates: Vec<NaiveDate> = p
+            .iter()
+            .filter(|(name, _, _)| name == "EXDATE")
+            .flat_map(|(_, params, value)| {
+                value
+                    .split(',')
+                    .filter_map(|v| parse_ics_datetime(params, v))
+                    .map(|(date, _)| date.date())
+            })
+            .collect();
+        exdates.extend(
+            overrides
+                .iter()
+                .filter(|(id, _)| Some(*id) == uid)
+                .map(|(_, date)| *date),
+        );
+
+        events.extend(rule.expand(&event, &exdates, horizon));
+    }
+
+    events
+}
+
+/// Name, parameters and value of a content line.
+type Property = (String, String, String);
+
+fn find_property<'a>(properties: &'a [Property], name: &str) -> Option<&'a Property> {
+    properties.iter().find(|(n, _, _)| n == name)
+}
+
+fn make_event(properties: &[Property]) -> Option<CalendarEvent> {
+    let property = |name: &str| find_property(properties, name);
+
+    let (_, start_params, start_value) = property("DTSTART")?;
+    let (start, all_day) = parse_ics_datetime(start_params, start_value)?;
+
+    let end_date = match property("DTEND").and_then(|(_, p, v)| parse_ics_datetime(p, v)) {
+        // DTEND is exclusive for all-day events and for events ending at midnight
+        Some((end, _)) if end.time() == NaiveTime::MIN && end.date() > start.date() => {
+            end.date() - Duration::days(1)
+        }
+        Some((end, _)) => end.date().max(start.date()),
+        None => start.date(),
+    };
+
+    Some(CalendarEvent {
+        summary: property("SUMMARY")
+            .map(|(_, _, v)| unescape_ics_text(v))
+            .unwrap_or_else(|| "(no title)".to_string()),
+        location: property("LOCATION")
+            .map(|(_, _, v)| unescape_ics_text(v))
+            .unwrap_or_default(),
+        start,
+        end_date,
+        all_day,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The part of an RRULE that is expanded: FREQ, INTERVAL, COUNT, UNTIL,
+/// BYDAY and BYMONTH. Weeks start on Monday.
+#[derive(Debug, PartialEq)]
+struct RecurrenceRule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    /// last start an occurrence may have, in local time
+    until: Option<NaiveDateTime>,
+    /// weekdays, with an optional position in the month (e.g. `-1FR`, the last Friday)
+    by_day: Vec<(Option<i32>, Weekday)>,
+    by_month: Vec<u32>,
+}
+
+impl RecurrenceRule {
+    /// Parses an RRULE value; rules with other parts (e.g. BYSETPOS) or
+    /// frequencies are not supported, and the event is shown once.
+    fn parse(value: &str) -> Option<Self> {
+        let mut frequency = None;
+        let mut rule = Self {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month: Vec::new(),
+        };
+
+        for part in value.trim().split(';') {
+            let (key, value) = part.split_once('=')?;
+            match key.to_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return None,
+                    })
+                }
+                "INTERVAL" => rule.interval = value.parse().ok().filter(|i| *i > 0)?,
+                "COUNT" => rule.count = Some(value.parse().ok()?),
+                "UNTIL" => {
+                    let (until, all_day) = parse_ics_datetime("", value)?;
+                    rule.until = Some(if all_day {
+                        until.date().and_time(NaiveTime::MIN) + Duration::days(1)
+                            - Duration::seconds(1)
+                    } else {
+                        until
+                    });
+                }
+                "BYDAY" => {
+                    rule.by_day = value
+                        .split(',')
+                        .map(parse_by_day)
+                        .collect::<Option<Vec<_>>>()?
+                }
+                "BYMONTH" => {
+                    rule.by_month = value
+                        .split(',')
+                        .map(|m| m.parse().ok().filter(|m| (1..=12).contains(m)))
+                        .collect::<Option<Vec<_>>>()?;
+                    rule.by_month.sort_unstable();
+                }
+                "WKST" => {}
+                _ => return None,
+            }
+        }
+
+        rule.frequency = frequency?;
+        Some(rule)
+    }
+
+    /// One event per occurrence of `event` up to `horizon`, skipping `exdates`.
+    fn expand(
+        &self,
+        event: &CalendarEvent,
+        exdates: &[NaiveDate],
+        horizon: NaiveDate,
+    ) -> Vec<CalendarEvent> {
+        let first = event.start.date();
+        let span = event.end_date - first;
+        let last = self
+            .until
+            .map_or(horizon, |until| until.date().min(horizon));
+
+        let mut events = Vec::new();
+        let mut count = 0;
+
+        for period in 0.. {
+            let Some((period_start, dates)) = self.period_dates(first, period) else {
+                break;
+            };
+            if period_start > last {
+                break;
+            }
+
+            for date in dates.into_iter().filter(|date| *date >= first) {
+                let start = date.and_time(event.start.time());
+                if date > last || self.until.is_some_and(|until| start > until) {
+                    return events;
+                }
+
+                // COUNT includes the excluded occurrences
+                count += 1;
+                if self.count.is_some_and(|max| count > max) {
+                    return events;
+                }
+
+                if !exdates.contains(&date) {
+                    events.push(CalendarEvent {
+                        start,
+                        end_date: date + span,
+                        ..event.clone()
+                    });
+                }
+            }
+        }
+
+        events
+    }
+
+    /// First day of the `period`th period after the one holding `first`,
+    /// and the dates in it that match the rule, in order.
+    fn period_dates(&self, first: NaiveDate, period: u32) -> Option<(NaiveDate, Vec<NaiveDate>)> {
+        let step = period.checked_mul(self.interval)?;
+
+        match self.frequency {
+            Frequency::Daily => {
+                let date = first.checked_add_days(Days::new(step as u64))?;
+                let dates = if self.matches_weekday(date) {
+                    vec![date]
+                } else {
+                    Vec::new()
+                };
+                Some((date, dates))
+            }
+            Frequency::Weekly => {
+                let monday = first
+                    .checked_sub_days(Days::new(first.weekday().num_days_from_monday() as u64))?
+                    .checked_add_days(Days::new(step as u64 * 7))?;
+                let week = (0..7).filter_map(|d| monday.checked_add_days(Days::new(d)));
+                let dates = if self.by_day.is_empty() {
+                    week.filter(|d| d.weekday() == first.weekday()).collect()
+                } else {
+                    week.filter(|d| self.matches_weekday(*d)).collect()
+                };
+                Some((monday, dates))
+            }
+            Frequency::Monthly => {
+                let month_start = first.with_day(1)?.checked_add_months(Months::new(step))?;
+                Some((month_start, self.dates_in_month(month_start, first.day())))
+            }
+            Frequency::Yearly => {
+                let year_start = NaiveDate::from_ymd_opt(first.year(), 1, 1)?
+                    .checked_add_months(Months::new(step.checked_mul(12)?))?;
+                let months = if self.by_month.is_empty() {
+                    vec![first.month()]
+                } else {
+                    self.by_month.clone()
+                };
+                let dates = months
+                    .into_iter()
+                    .filter_map(|month| year_start.with_month(month))
+                    .flat_map(|month_start| self.dates_in_month(month_start, first.day()))
+                    .collect();
+                Some((year_start, dates))
+            }
+        }
+    }
+
+    fn matches_weekday(&self, date: NaiveDate) -> bool {
+        self.by_day.is_empty() || self.by_day.iter().any(|(_, day)| *day == date.weekday())
+    }
+
+    /// Dates of the month starting at `month_start` that match BYDAY, or
+    /// `day` itself without BYDAY (none when the month is too short).
+    fn dates_in_month(&self, month_start: NaiveDate, day: u32) -> Vec<NaiveDate> {
+        if self.by_day.is_empty() {
+            return month_start.with_day(day).into_iter().collect();
+        }
+
+        let month: Vec<NaiveDate> = month_start
+            .iter_days()
+            .take_while(|d| d.month() == month_start.month())
+            .collect();
+
+        let mut dates: Vec<NaiveDate> = self
+            .by_day
+            .iter()
+            .flat_map(|(position, weekday)| {
+                let matching: Vec<NaiveDate> = month
+                    .iter()
+                    .copied()
+                    .filter(|d| d.weekday() == *weekday)
+                    .collect();
+                match position {
+                    None => matching,
+                    Some(n) if *n > 0 => {
+                        matching.get(*n as usize - 1).copied().into_iter().collect()
+                    }
+                    Some(n) => matching
+                        .len()
+                        .checked_sub(n.unsigned_abs() as usize)
+                        .and_then(|i| matching.get(i))
+                        .copied()
+                        .into_iter()
+                        .collect(),
+                }
+            })
+            .collect();
+        dates.sort_unstable();
+        dates.dedup();
+        dates
+    }
+}
+
+/// Parses a BYDAY entry such as `MO`, `2TU` or `-1FR`.
+fn parse_by_day(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let value = value.trim();
+    let split = value.len().checked_sub(2)?;
+    let weekday = match value.get(split..)?.to_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+
+    let position = match value.get(..split)? {
+        "" => None,
+        position => Some(position.parse::<i32>().ok().filter(|n| *n != 0)?),
+    };
+
+    Some((position, weekday))
+}
+
+/// Parses DATE and DATE-TIME values (floating, UTC or with TZID) into local time.
+fn parse_ics_datetime(params: &str, value: &str) -> Option<(NaiveDateTime, bool)> {
+    let value = value.trim();
+
+    if value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some((date.and_time(NaiveTime::MIN), true));
+    }
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        let local = Utc.from_utc_datetime(&naive).with_timezone(&Local);
+        return Some((local.naive_local(), false));
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+
+    let tzid = params
+        .split(';')
+        .find_map(|p| p.strip_prefix("TZID="))
+        .and_then(|tz| tz.trim_matches('"').parse::<Tz>().ok());
+
+    match tzid {
+        Some(tz) => {
+            let zoned = tz.from_local_datetime(&naive).earliest()?;
+            Some((zoned.with_timezone(&Local).naive_local(), false))
+        }
+        None => Some((naive, false)),
+    }
+}
+
+/// Undoes the TEXT escapes `\\`, `\;`, `\,` and `\n` in one pass, so an
+/// escaped backslash is never read as the start of another escape. Line
+/// breaks become spaces, as events are shown on one line.
+fn unescape_ics_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some('n' | 'N') => text.push(' '),
+                Some(escaped) => text.push(escaped),
+                None => {}
+            },
+            _ => text.push(ch),
+        }
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    fn event(body: &str) -> CalendarEvent {
+        let ics = format!(
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\n{}\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+            body.replace('\n', "\r\n")
+        );
+        let mut events = parse(&ics);
+        assert_eq!(events.len(), 1);
+        events.remove(0)
+    }
+
+    fn parse(ics: &str) -> Vec<CalendarEvent> {
+        parse_ics(ics, NaiveDate::from_ymd_opt(2030, 1, 1).unwrap())
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    /// Start dates of the occurrences of one recurring VEVENT.
+    fn occurrences(body: &str) -> Vec<NaiveDate> {
+        let ics = format!("BEGIN:VEVENT\n{}\nEND:VEVENT\n", body);
+        parse(&ics).iter().map(|e| e.start.date()).collect()
+    }
+
+    #[test]
+    fn unfolds_continuation_lines() {
+        let event = event(
+            "DTSTART:20260310T090000\n\
+             SUMMARY:Quarterly planning with the\n  whole team\n\
+             LOCATION:Room\n\t4",
+        );
+        assert_eq!(event.summary, "Quarterly planning with the whole team");
+        assert_eq!(event.location, "Room4");
+    }
+
+    #[test]
+    fn all_day_events_end_the_day_before_dtend() {
+        let event = event(
+            "DTSTART;VALUE=DATE:20260310\n\
+             DTEND;VALUE=DATE:20260312\n\
+             SUMMARY:Conference",
+        );
+        assert!(event.all_day);
+        assert_eq!(event.start, at(2026, 3, 10, 0, 0));
+        assert_eq!(
+            event.end_date,
+            NaiveDate::from_ymd_opt(2026, 3, 11).unwrap()
+        );
+    }
+
+    #[test]
+    fn floating_times_are_kept_and_untitled_events_named() {
+        let event = event("DTSTART:20260310T091500");
+        assert!(!event.all_day);
+        assert_eq!(event.start, at(2026, 3, 10, 9, 15));
+        assert_eq!(event.end_date, event.start.date());
+        assert_eq!(event.summary, "(no title)");
+    }
+
+    #[test]
+    fn utc_times_are_converted_to_local_time() {
+        let event = event("DTSTART:20260310T120000Z");
+        let expected = Utc
+            .from_utc_datetime(&at(2026, 3, 10, 12, 0))
+            .with_timezone(&Local)
+            .naive_local();
+        assert_eq!(event.start, expected);
+    }
+
+    #[test]
+    fn tzid_times_are_converted_to_local_time() {
+        let zoned = event("DTSTART;TZID=\"America/New_York\":20260710T080000");
+        let expected = "America/New_York"
+            .parse::<Tz>()
+            .unwrap()
+            .from_local_datetime(&at(2026, 7, 10, 8, 0))
+            .unwrap()
+            .with_timezone(&Local)
+            .naive_local();
+        assert_eq!(zoned.start, expected);
+
+        // an unknown zone falls back to floating time
+        let floating = event("DTSTART;TZID=Mars/Olympus:20260710T080000");
+        assert_eq!(floating.start, at(2026, 7, 10, 8, 0));
+    }
+
+    #[test]
+    fn unescapes_text() {
+        assert_eq!(
+            unescape_ics_text(r"Lunch\, then talks\; bring a laptop\nRoom 2"),
+            "Lunch, then talks; bring a laptop Room 2"
+        );
+        assert_eq!(unescape_ics_text(r"C:\\new\\Notes"), r"C:\new\Notes");
+        assert_eq!(unescape_ics_text(r"trailing\"), "trailing");
+    }
+
+    #[test]
+    fn malformed_input_is_skipped() {
+        let ics = "END:VEVENT\n\
+                   SUMMARY:outside any event\n\
+                   BEGIN:VEVENT\n\
+                   SUMMARY:no start\n\
+                   END:VEVENT\n\
+                   BEGIN:VEVENT\n\
+                   DTSTART:2026-03-10\n\
+                   END:VEVENT\n\
+                   BEGIN:VEVENT\n\
+                   a line without a colon\n\
+                   DTSTART:20261301T000000\n\
+                   END:VEVENT\n\
+                   BEGIN:VEVENT\n\
+                   DTSTART;VALUE=DATE:20260310\n\
+                   SUMMARY:kept\n\
+                   END:VEVENT\n\
+                   BEGIN:VEVENT\n\
+                   DTSTART:20260311T090000\n\
+                   SUMMARY:never ended\n";
+
+        let events = parse(ics);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "kept");
+        assert!(parse("").is_empty());
+        assert!(parse(" \n\t\n:\n;:").is_empty());
+    }
+
+    #[test]
+    fn daily_events_repeat_every_interval_days() {
+        assert_eq!(
+            occurrences("DTSTART:20260310T090000\nRRULE:FREQ=DAILY;INTERVAL=2;COUNT=3"),
+            vec![date(2026, 3, 10), date(2026, 3, 12), date(2026, 3, 14)]
+        );
+        // only on weekdays, until a date
+        assert_eq!(
+            occurrences(
+                "DTSTART:20260313T090000\nRRULE:FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR;UNTIL=20260317"
+            ),
+            vec![date(2026, 3, 13), date(2026, 3, 16), date(2026, 3, 17)]
+        );
+    }
+
+    #[test]
+    fn weekly_events_repeat_on_their_weekdays() {
+        // Tuesday 2026-03-10, every other week on Tuesday and Thursday
+        assert_eq!(
+            occurrences(
+                "DTSTART:20260310T090000\nRRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH;COUNT=5"
+            ),
+            vec![
+                date(2026, 3, 10),
+                date(2026, 3, 12),
+                date(2026, 3, 24),
+                date(2026, 3, 26),
+                date(2026, 4, 7),
+            ]
+        );
+        // without BYDAY on the weekday of DTSTART, until a UTC time
+        assert_eq!(
+            occurrences("DTSTART:20260310T090000\nRRULE:FREQ=WEEKLY;UNTIL=20260324T235959Z").len(),
+            3
+        );
+    }
+
+    #[test]
+    fn monthly_events_repeat_on_the_day_or_weekday_position() {
+        // the 31st is skipped in shorter months
+        assert_eq!(
+            occurrences("DTSTART;VALUE=DATE:20260131\nRRULE:FREQ=MONTHLY;COUNT=3"),
+            vec![date(2026, 1, 31), date(2026, 3, 31), date(2026, 5, 31)]
+        );
+        // second Tuesday and last Friday
+        assert_eq!(
+            occurrences("DTSTART:20260101T180000\nRRULE:FREQ=MONTHLY;BYDAY=2TU,-1FR;COUNT=4"),
+            vec![
+                date(2026, 1, 13),
+                date(2026, 1, 30),
+                date(2026, 2, 10),
+                date(2026, 2, 27),
+            ]
+        );
+    }
+
+    #[test]
+    fn yearly_events_repeat_until_the_horizon() {
+        let birthdays = occurrences("DTSTART;VALUE=DATE:20240229\nRRULE:FREQ=YEARLY");
+        assert_eq!(birthdays, vec![date(2024, 2, 29), date(2028, 2, 29)]);
+
+        // fourth Thursday of November
+        assert_eq!(
+            occurrences("DTSTART;VALUE=DATE:20261126\nRRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=4TH"),
+            vec![
+                date(2026, 11, 26),
+                date(2027, 11, 25),
+                date(2028, 11, 23),
+                date(2029, 11, 22)
+            ]
+        );
+    }
+
+    #[test]
+    fn occurrences_keep_the_duration_and_details() {
+        let events = parse(
+            "BEGIN:VEVENT\n\
+             DTSTART;VALUE=DATE:20260310\n\
+             DTEND;VALUE=DATE:20260312\n\
+             SUMMARY:Offsite\n\
+             RRULE:FREQ=MONTHLY;COUNT=2\n\
+             END:VEVENT\n",
+        );
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].summary, "Offsite");
+        assert!(events[1].all_day);
+        assert_eq!(events[1].start, at(2026, 4, 10, 0, 0));
+        assert_eq!(events[1].end_date, date(2026, 4, 11));
+    }
+
+    #[test]
+    fn excluded_and_moved_occurrences_are_skipped() {
+        let events = parse(
+            "BEGIN:VEVENT\n\
+             UID:standup\n\
+             DTSTART;TZID=Europe/Berlin:20260309T093000\n\
+             RRULE:FREQ=DAILY;COUNT=5\n\
+             EXDATE;TZID=Europe/Berlin:20260310T093000,20260311T093000\n\
+             EXDATE;VALUE=DATE:20260312\n\
+             END:VEVENT\n\
+             BEGIN:VEVENT\n\
+             UID:standup\n\
+             RECURRENCE-ID;TZID=Europe/Berlin:20260313T093000\n\
+             DTSTART;TZID=Europe/Berlin:20260313T113000\n\
+             SUMMARY:moved\n\
+             END:VEVENT\n",
+        );
+
+        let mut starts: Vec<NaiveDate> = events.iter().map(|e| e.start.date()).collect();
+        starts.sort();
+        assert_eq!(starts, vec![date(2026, 3, 9), date(2026, 3, 13)]);
+        assert!(events.iter().any(|e| e.summary == "moved"));
+    }
+
+    #[test]
+    fn unsupported_rules_show_the_first_occurrence() {
+        assert_eq!(
+            occurrences("DTSTART:20260310T090000\nRRULE:FREQ=HOURLY;COUNT=3"),
+            vec![date(2026, 3, 10)]
+        );
+        assert_eq!(
+            occurrences("DTSTART:20260310T090000\nRRULE:FREQ=MONTHLY;BYSETPOS=-1;BYDAY=MO,FR"),
+            vec![date(2026, 3, 10)]
+        );
+        assert_eq!(parse_by_day("0MO"), None);
+        assert_eq!(parse_by_day("-1FR"), Some((Some(-1), Weekday::Fri)));
+    }
+}
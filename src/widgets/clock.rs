@@ -2,148 +2,177 @@ use chrono::{Local, Timelike};
 use ratatui::{
     Frame,
     layout::Rect,
-    text::{Line, Span, Text},
-    widgets::{Block, Borders, Paragraph},
+    style::Style,
+    text::{Line, Text},
+    widgets::{Block, Paragraph},
+};
+
+use crate::{
+    alert::{self, Alert},
+    config::{CONFIG, WorldClockPosition},
+    widgets::{
+        clock_font::{ClockFont, pick_font},
+        world_clock::WorldClock,
+    },
 };
 
 pub struct ClockWidget;
 
 impl ClockWidget {
-    pub fn render(frame: &mut Frame, area: Rect) {
-        let area = area;
+    /// Height the clock needs in a frame of `frame_area`, including the optional date line.
+    ///
+    /// With `font = "auto"` the clock may take up to a third of the frame, so bigger
+    /// terminals get bigger fonts; a named font keeps its height while it fits.
+    pub fn height(frame_area: Rect) -> u16 {
+        let budget = if CONFIG().clock.font == "auto" {
+            frame_area.height / 3
+        } else {
+            frame_area.height
+        }
+        .saturating_sub(Self::extra_lines());
+        let font = pick_font(&Self::time_string(), frame_area.width, budget);
+
+        font.height as u16 + Self::extra_lines()
+    }
 
-        let hour = Local::now().hour();
-        let minute = Local::now().minute();
+    pub fn render(frame: &mut Frame, area: Rect) {
+        let alert = alert::active();
+        let time = alert
+            .as_ref()
+            .and_then(|alert| alert.digits.clone())
+            .unwrap_or_else(Self::time_string);
+        let font = pick_font(
+            &time,
+            area.width,
+            area.height.saturating_sub(Self::extra_lines()),
+        );
+        let hour_text = Self::get_number_text(font, &time);
 
-        let hour_text =
-            Self::get_number_text(&[hour / 10, hour % 10, 10, minute / 10, minute % 10]);
+        let width = font.text_width(&time) as u16;
+        let height = font.height as u16;
 
-        let width = (hour_text
-            .lines
-            .iter()
-            .map(|line| line.width())
-            .max()
-            .unwrap_or(0)
-            - 1) as u16;
+        let style = Self::alert_style(alert.as_ref());
 
-        let height = 6 as u16;
+        if alert.is_some() {
+            frame.render_widget(Block::default().style(style), area);
+        }
 
         let block = Block::default();
 
         let content_area = Rect {
             x: area.x + (area.width.saturating_sub(width)) / 2,
-            y: 0,
-            width,
-            height,
+            y: area.y,
+            width: width.min(area.width),
+            height: height.min(area.height),
         };
 
-        let paragraph = Paragraph::new(hour_text).block(block).centered();
+        let paragraph = Paragraph::new(hour_text)
+            .block(block)
+            .style(style)
+            .centered();
 
         frame.render_widget(paragraph, content_area);
-    }
 
-    fn get_number_text(digits: &[u32]) -> Text<'static> {
-        let mut text = Text::default();
-
-        for line_number in 0..5 {
-            let mut line = Line::default();
-            for &digit in digits {
-                if digit > 11 {
-                    continue;
-                }
-                line.push_span(Span::from(ASCII_DIGITS[digit as usize][line_number]));
-                line.push_span(Span::from(" "));
+        if let Some(alert) = &alert
+            && area.height > height
+        {
+            let keybinds = &CONFIG().keybinds;
+            let message = format!(
+                "⏰ {}   {}: dismiss  {}: snooze",
+                alert.title, keybinds.accept, keybinds.cancel
+            );
+            let message_area = Rect {
+                x: area.x,
+                y: area.y + height,
+                width: area.width,
+                height: 1,
+            };
+
+            frame.render_widget(
+                Paragraph::new(message).style(style).centered(),
+                message_area,
+            );
+        }
+
+        let mut info_lines = Vec::new();
+
+        if CONFIG().clock.show_date {
+            let date = Local::now().format(&CONFIG().clock.date_format).to_string();
+            info_lines.push(Line::from(date));
+        }
+
+        if Self::shows_world_clock() {
+            info_lines.push(WorldClock::compact_line());
+        }
+
+        for (i, line) in info_lines.into_iter().enumerate() {
+            let y = area.y + height + 1 + i as u16;
+            if y >= area.y + area.height {
+                break;
             }
-            text.lines.push(line);
+
+            let line_area = Rect {
+                x: area.x,
+                y,
+                width: area.width,
+                height: 1,
+            };
+
+            frame.render_widget(Paragraph::new(line).centered(), line_area);
+        }
+    }
+
+    /// Flashes between inverted and highlighted theme colors every second while an alert is active.
+    fn alert_style(alert: Option<&Alert>) -> Style {
+        let Some(alert) = alert else {
+            return Style::default();
+        };
+
+        let theme = &CONFIG().themes;
+        if (Local::now() - alert.raised_at).num_seconds() % 2 == 0 {
+            Style::default()
+                .fg(theme.bg_color)
+                .bg(theme.content_selected_color)
+        } else {
+            Style::default().fg(theme.content_selected_color)
         }
+    }
 
-        text
+    /// Lines below the digits: a spacer, the optional date and the optional world clock.
+    fn extra_lines() -> u16 {
+        1 + CONFIG().clock.show_date as u16 + Self::shows_world_clock() as u16
+    }
+
+    fn shows_world_clock() -> bool {
+        let world_clock = &CONFIG().world_clock;
+        world_clock.position == WorldClockPosition::Clock && !world_clock.zones.is_empty()
+    }
+
+    /// Builds the text shown in big digits, e.g. "09:41", "21:41:07" or "09:41 PM".
+    fn time_string() -> String {
+        let now = Local::now();
+        let clock = &CONFIG().clock;
+
+        let (is_pm, hour) = if clock.twelve_hour {
+            now.hour12()
+        } else {
+            (false, now.hour())
+        };
+
+        let mut time = format!("{:02}:{:02}", hour, now.minute());
+
+        if clock.show_seconds {
+            time.push_str(&format!(":{:02}", now.second()));
+        }
+
+        if clock.twelve_hour {
+            time.push_str(if is_pm { " PM" } else { " AM" });
+        }
+
+        time
     }
-}
 
-const ASCII_DIGITS: [[&str; 5]; 11] = [
-    [
-        // 0
-        "██████",
-        "██  ██",
-        "██  ██",
-        "██  ██",
-        "██████",
-    ],
-    [
-        // 1
-        "  ██  ",
-        "████  ",
-        "  ██  ",
-        "  ██  ",
-        "██████",
-    ],
-    [
-        // 2
-        "██████",
-        "    ██",
-        "██████",
-        "██    ",
-        "██████",
-    ],
-    [
-        // 3
-        "██████",
-        "    ██",
-        "██████",
-        "    ██",
-        "██████",
-    ],
-    [
-        // 4
-        "██  ██",
-        "██  ██",
-        "██████",
-        "    ██",
-        "    ██",
-    ],
-    [
-        // 5
-        "██████",
-        "██    ",
-        "██████",
-        "    ██",
-        "██████",
-    ],
-    [
-        // 6
-        "██████",
-        "██    ",
-        "██████",
-        "██  ██",
-        "██████",
-    ],
-    [
-        // 7
-        "██████",
-        "    ██",
-        "   ██ ",
-        "  ██  ",
-        " ██   ",
-    ],
-    [
-        // 8
-        "██████",
-        "██  ██",
-        "██████",
-        "██  ██",
-        "██████",
-    ],
-    [
-        // 9
-        "██████",
-        "██  ██",
-        "██████",
-        "    ██",
-        "██████",
-    ],
-    [
-        // :
-        "    ", " ██ ", "    ", " ██ ", "    ",
-    ],
-];
+    fn get_number_text(font: &ClockFont, time: &str) -> Text<'static> {
+        font.render_text(time)
+    }
+}
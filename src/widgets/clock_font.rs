@@ -0,0 +1,465 @@
+//! Big-digit fonts for the clock.
+//!
+//! Ships a set of built-in fonts and optionally loads a custom font from
+//! `~/.config/aex/`. The clock asks [`pick_font`] for the largest font that
+//! fits the area it gets.
+
+use color_eyre::eyre::{Result, eyre};
+use once_cell::sync::OnceCell;
+use ratatui::text::{Line, Span, Text};
+use serde::Deserialize;
+use std::{collections::HashMap, fs};
+use unicode_width::UnicodeWidthStr;
+
+use crate::config::{BUILTIN_FONT_NAMES, CONFIG, aex_config_dir};
+
+/// Characters every font has to define.
+const REQUIRED_GLYPHS: &str = "0123456789:APM";
+
+/// A big-digit font: every glyph has `height` rows of equal width.
+#[derive(Debug)]
+pub struct ClockFont {
+    pub name: String,
+    pub height: usize,
+    glyphs: HashMap<char, Vec<String>>,
+}
+
+/// Mirrors a custom font file, e.g. `~/.config/aex/font.toml`.
+#[derive(Debug, Deserialize)]
+struct RawFont {
+    name: Option<String>,
+    glyphs: HashMap<String, Vec<String>>,
+}
+
+impl ClockFont {
+    /// Renders `text` with one column of spacing between glyphs; unknown characters are skipped.
+    pub fn render_text(&self, text: &str) -> Text<'static> {
+        let mut lines = Vec::new();
+
+        for row in 0..self.height {
+            let mut line = Line::default();
+            for (i, glyph) in text.chars().filter_map(|ch| self.glyph(ch)).enumerate() {
+                if i > 0 {
+                    line.push_span(Span::from(" "));
+                }
+                line.push_span(Span::from(glyph[row].clone()));
+            }
+            lines.push(line);
+        }
+
+        Text::from(lines)
+    }
+
+    /// Width in columns that `text` takes when rendered with this font.
+    pub fn text_width(&self, text: &str) -> usize {
+        let widths: Vec<usize> = text
+            .chars()
+            .filter_map(|ch| self.glyph(ch))
+            .map(|glyph| glyph_width(glyph))
+            .collect();
+
+        widths.iter().sum::<usize>() + widths.len().saturating_sub(1)
+    }
+
+    fn glyph(&self, ch: char) -> Option<&Vec<String>> {
+        self.glyphs.get(&ch)
+    }
+
+    fn from_rows(name: &str, glyphs: Vec<(char, Vec<String>)>) -> Self {
+        let height = glyphs.first().map(|(_, rows)| rows.len()).unwrap_or(0);
+        Self {
+            name: name.to_string(),
+            height,
+            glyphs: glyphs.into_iter().collect(),
+        }
+    }
+
+    /// Loads a custom font from a toml file with a `[glyphs]` table of row arrays.
+    fn load(path: &std::path::Path) -> Result<Self> {
+        let font_str = fs::read_to_string(path)?;
+        Self::parse(&font_str, &path.display().to_string())
+    }
+
+    /// Parses and checks a custom font; `source` names it in errors.
+    fn parse(font_str: &str, source: &str) -> Result<Self> {
+        let raw: RawFont = toml::from_str(font_str)?;
+
+        let mut glyphs = HashMap::new();
+        for (key, rows) in raw.glyphs {
+            let mut chars = key.chars();
+            let (Some(ch), None) = (chars.next(), chars.next()) else {
+                return Err(eyre!("Invalid glyph key in font: {:?}", key));
+            };
+            glyphs.insert(ch, rows);
+        }
+
+        for ch in REQUIRED_GLYPHS.chars() {
+            if !glyphs.contains_key(&ch) {
+                return Err(eyre!("Font {} is missing glyph {:?}", source, ch));
+            }
+        }
+
+        let height = glyphs[&'0'].len();
+        for (ch, rows) in &glyphs {
+            if rows.len() != height {
+                return Err(eyre!("Glyph {:?} must have {} rows", ch, height));
+            }
+            let width = glyph_width(rows);
+            if rows.iter().any(|row| row.width() != width) {
+                return Err(eyre!("Rows of glyph {:?} must have equal width", ch));
+            }
+        }
+
+        glyphs
+            .entry(' ')
+            .or_insert_with(|| vec![" ".to_string(); height]);
+
+        // `pick_font` finds fonts by name, so a taken name would hide the custom font
+        let name = raw.name.unwrap_or_else(|| "custom".to_string());
+        if name == "auto" || BUILTIN_FONT_NAMES.contains(&name.as_str()) {
+            return Err(eyre!(
+                "Custom font {} is named {:?}, which is reserved for a built-in font",
+                source,
+                name
+            ));
+        }
+
+        Ok(Self {
+            name,
+            height,
+            glyphs,
+        })
+    }
+}
+
+static FONTS_CELL: OnceCell<Vec<ClockFont>> = OnceCell::new();
+
+/// Builds the built-in fonts and loads `[clock] custom_font`, if set.
+pub fn init_fonts() -> Result<()> {
+    let mut fonts = builtin_fonts();
+
+    if let Some(file) = &CONFIG().clock.custom_font {
+        let mut font_path = aex_config_dir()?;
+        font_path.push(file);
+        fonts.push(ClockFont::load(&font_path)?);
+    }
+
+    // `Config::load` only knows the built-in names; a custom font names itself
+    let preferred = &CONFIG().clock.font;
+    if preferred != "auto" && !fonts.iter().any(|font| &font.name == preferred) {
+        return Err(eyre!("Unknown clock font: {}", preferred));
+    }
+
+    FONTS_CELL
+        .set(fonts)
+        .map_err(|_| eyre!("Fonts already initialized"))?;
+    Ok(())
+}
+
+pub fn fonts() -> &'static [ClockFont] {
+    FONTS_CELL.get().expect("Fonts not initialized")
+}
+
+/// Picks the configured font, or the largest one that fits if the configured one does not.
+pub fn pick_font(text: &str, width: u16, height: u16) -> &'static ClockFont {
+    pick_from(fonts(), &CONFIG().clock.font, text, width, height)
+}
+
+fn pick_from<'a>(
+    fonts: &'a [ClockFont],
+    preferred: &str,
+    text: &str,
+    width: u16,
+    height: u16,
+) -> &'a ClockFont {
+    let fits = |font: &ClockFont| {
+        font.text_width(text) <= width as usize && font.height <= height as usize
+    };
+
+    if let Some(font) = fonts.iter().find(|f| f.name == preferred)
+        && fits(font)
+    {
+        return font;
+    }
+
+    fonts
+        .iter()
+        .filter(|font| fits(font))
+        .max_by_key(|font| font.text_width(text) * font.height)
+        .unwrap_or_else(|| {
+            fonts
+                .iter()
+                .min_by_key(|font| font.text_width(text) * font.height)
+                .expect("No fonts available")
+        })
+}
+
+fn glyph_width(rows: &[String]) -> usize {
+    rows.iter().map(|row| row.width()).max().unwrap_or(0)
+}
+
+// ====== Built-in Fonts ======
+
+fn builtin_fonts() -> Vec<ClockFont> {
+    let block: Vec<(char, Vec<String>)> = BLOCK_GLYPHS
+        .iter()
+        .map(|(ch, rows)| (*ch, rows.iter().map(|r| r.to_string()).collect()))
+        .collect();
+
+    let double = block
+        .iter()
+        .map(|(ch, rows)| {
+            let doubled = rows.iter().flat_map(|r| [r.clone(), r.clone()]).collect();
+            (*ch, doubled)
+        })
+        .collect();
+
+    let from_bitmaps = |convert: fn(&[&str]) -> Vec<String>| {
+        BITMAP_GLYPHS
+            .iter()
+            .map(|(ch, bitmap)| (*ch, convert(bitmap)))
+            .collect()
+    };
+
+    let from_table = |table: &[(char, [&str; 3])]| {
+        table
+            .iter()
+            .map(|(ch, rows)| (*ch, rows.iter().map(|r| r.to_string()).collect()))
+            .collect()
+    };
+
+    // in `BUILTIN_FONT_NAMES` order
+    let glyph_sets: [Vec<(char, Vec<String>)>; 6] = [
+        double,
+        block,
+        from_bitmaps(bitmap_to_small),
+        from_table(&THIN_GLYPHS),
+        from_table(&SEVEN_SEGMENT_GLYPHS),
+        from_bitmaps(bitmap_to_braille),
+    ];
+
+    BUILTIN_FONT_NAMES
+        .into_iter()
+        .zip(glyph_sets)
+        .map(|(name, glyphs)| ClockFont::from_rows(name, glyphs))
+        .collect()
+}
+
+/// One `█` per pixel.
+fn bitmap_to_small(bitmap: &[&str]) -> Vec<String> {
+    bitmap
+        .iter()
+        .map(|row| {
+            row.chars()
+                .map(|p| if p == '#' { '█' } else { ' ' })
+                .collect()
+        })
+        .collect()
+}
+
+/// Packs 2x4 pixels into one braille cell.
+fn bitmap_to_braille(bitmap: &[&str]) -> Vec<String> {
+    const DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+    let pixel = |x: usize, y: usize| {
+        bitmap
+            .get(y)
+            .and_then(|row| row.chars().nth(x))
+            .is_some_and(|p| p == '#')
+    };
+    let width = bitmap.iter().map(|row| row.len()).max().unwrap_or(0);
+
+    (0..bitmap.len().div_ceil(4))
+        .map(|cell_y| {
+            (0..width.div_ceil(2))
+                .map(|cell_x| {
+                    let mut bits = 0;
+                    for (dx, column) in DOTS.iter().enumerate() {
+                        for (dy, dot) in column.iter().enumerate() {
+                            if pixel(cell_x * 2 + dx, cell_y * 4 + dy) {
+                                bits |= dot;
+                            }
+                        }
+                    }
+                    char::from_u32(0x2800 + bits).unwrap_or(' ')
+                })
+                .collect()
+        })
+        .collect()
+}
+
+const BLOCK_GLYPHS: [(char, [&str; 5]); 15] = [
+    ('0', ["██████", "██  ██", "██  ██", "██  ██", "██████"]),
+    ('1', ["  ██  ", "████  ", "  ██  ", "  ██  ", "██████"]),
+    ('2', ["██████", "    ██", "██████", "██    ", "██████"]),
+    ('3', ["██████", "    ██", "██████", "    ██", "██████"]),
+    ('4', ["██  ██", "██  ██", "██████", "    ██", "    ██"]),
+    ('5', ["██████", "██    ", "██████", "    ██", "██████"]),
+    ('6', ["██████", "██    ", "██████", "██  ██", "██████"]),
+    ('7', ["██████", "    ██", "   ██ ", "  ██  ", " ██   "]),
+    ('8', ["██████", "██  ██", "██████", "██  ██", "██████"]),
+    ('9', ["██████", "██  ██", "██████", "    ██", "██████"]),
+    (':', ["    ", " ██ ", "    ", " ██ ", "    "]),
+    ('A', ["██████", "██  ██", "██████", "██  ██", "██  ██"]),
+    ('P', ["██████", "██  ██", "██████", "██    ", "██    "]),
+    (
+        'M',
+        [
+            "██      ██",
+            "████  ████",
+            "██  ██  ██",
+            "██      ██",
+            "██      ██",
+        ],
+    ),
+    (' ', [" ", " ", " ", " ", " "]),
+];
+
+/// 3x5 pixel shapes the small and braille fonts are generated from.
+const BITMAP_GLYPHS: [(char, [&str; 5]); 15] = [
+    ('0', ["###", "#.#", "#.#", "#.#", "###"]),
+    ('1', [".#.", "##.", ".#.", ".#.", "###"]),
+    ('2', ["###", "..#", "###", "#..", "###"]),
+    ('3', ["###", "..#", "###", "..#", "###"]),
+    ('4', ["#.#", "#.#", "###", "..#", "..#"]),
+    ('5', ["###", "#..", "###", "..#", "###"]),
+    ('6', ["###", "#..", "###", "#.#", "###"]),
+    ('7', ["###", "..#", "..#", ".#.", ".#."]),
+    ('8', ["###", "#.#", "###", "#.#", "###"]),
+    ('9', ["###", "#.#", "###", "..#", "###"]),
+    (':', [".", "#", ".", "#", "."]),
+    ('A', ["###", "#.#", "###", "#.#", "#.#"]),
+    ('P', ["###", "#.#", "###", "#..", "#.."]),
+    ('M', ["#...#", "##.##", "#.#.#", "#...#", "#...#"]),
+    (' ', [".", ".", ".", ".", "."]),
+];
+
+const THIN_GLYPHS: [(char, [&str; 3]); 15] = [
+    ('0', ["┌─┐", "│ │", "└─┘"]),
+    ('1', ["╶┐ ", " │ ", "╶┴╴"]),
+    ('2', ["╶─┐", "┌─┘", "└─╴"]),
+    ('3', ["╶─┐", " ─┤", "╶─┘"]),
+    ('4', ["╷ ╷", "└─┤", "  ╵"]),
+    ('5', ["┌─╴", "└─┐", "╶─┘"]),
+    ('6', ["┌─╴", "├─┐", "└─┘"]),
+    ('7', ["╶─┐", "  │", "  ╵"]),
+    ('8', ["┌─┐", "├─┤", "└─┘"]),
+    ('9', ["┌─┐", "└─┤", "╶─┘"]),
+    (':', [" ", "∶", " "]),
+    ('A', ["┌─┐", "├─┤", "╵ ╵"]),
+    ('P', ["┌─┐", "├─┘", "╵  "]),
+    ('M', ["┌┬┐", "│││", "╵╵╵"]),
+    (' ', [" ", " ", " "]),
+];
+
+const SEVEN_SEGMENT_GLYPHS: [(char, [&str; 3]); 15] = [
+    ('0', [" _ ", "| |", "|_|"]),
+    ('1', ["   ", "  |", "  |"]),
+    ('2', [" _ ", " _|", "|_ "]),
+    ('3', [" _ ", " _|", " _|"]),
+    ('4', ["   ", "|_|", "  |"]),
+    ('5', [" _ ", "|_ ", " _|"]),
+    ('6', [" _ ", "|_ ", "|_|"]),
+    ('7', [" _ ", "  |", "  |"]),
+    ('8', [" _ ", "|_|", "|_|"]),
+    ('9', [" _ ", "|_|", " _|"]),
+    (':', [" ", ".", "."]),
+    ('A', [" _ ", "|_|", "| |"]),
+    ('P', [" _ ", "|_|", "|  "]),
+    ('M', [" _ _ ", "| | |", "| | |"]),
+    (' ', [" ", " ", " "]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A two-row font defining every required glyph, plus `extra` toml lines.
+    fn font_toml(name: Option<&str>, extra: &str, skip: char) -> String {
+        let mut toml = name.map_or(String::new(), |n| format!("name = {:?}\n", n));
+        toml.push_str("[glyphs]\n");
+        for ch in REQUIRED_GLYPHS.chars().filter(|ch| *ch != skip) {
+            toml.push_str(&format!(
+                "{:?} = [\"{ch}{ch}\", \"{ch}{ch}\"]\n",
+                ch.to_string()
+            ));
+        }
+        toml.push_str(extra);
+        toml
+    }
+
+    fn parse(toml: &str) -> Result<ClockFont> {
+        ClockFont::parse(toml, "font.toml")
+    }
+
+    fn error(toml: &str) -> String {
+        parse(toml).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn parses_a_custom_font() {
+        let font = parse(&font_toml(Some("tiny"), "", ' ')).unwrap();
+        assert_eq!(font.name, "tiny");
+        assert_eq!(font.height, 2);
+        assert_eq!(font.text_width("12:30"), 14);
+        assert_eq!(font.text_width(" "), 1);
+
+        let unnamed = parse(&font_toml(None, "", ' ')).unwrap();
+        assert_eq!(unnamed.name, "custom");
+    }
+
+    #[test]
+    fn rejects_missing_glyphs_and_bad_keys() {
+        assert_eq!(
+            error(&font_toml(None, "", ':')),
+            "Font font.toml is missing glyph ':'"
+        );
+        assert_eq!(
+            error(&font_toml(None, "\"10\" = [\"x\", \"x\"]\n", ' ')),
+            "Invalid glyph key in font: \"10\""
+        );
+        assert!(parse("glyphs = 3").is_err());
+    }
+
+    #[test]
+    fn rejects_uneven_glyphs() {
+        assert_eq!(
+            error(&font_toml(None, "\"-\" = [\"--\"]\n", ' ')),
+            "Glyph '-' must have 2 rows"
+        );
+        assert_eq!(
+            error(&font_toml(None, "\"-\" = [\"--\", \"-\"]\n", ' ')),
+            "Rows of glyph '-' must have equal width"
+        );
+    }
+
+    #[test]
+    fn rejects_reserved_names() {
+        for name in ["auto", "block", "braille"] {
+            assert_eq!(
+                error(&font_toml(Some(name), "", ' ')),
+                format!(
+                    "Custom font font.toml is named {:?}, which is reserved for a built-in font",
+                    name
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn picks_the_preferred_font_while_it_fits() {
+        let fonts = builtin_fonts();
+        assert_eq!(pick_from(&fonts, "small", "12:34", 80, 40).name, "small");
+        assert_eq!(pick_from(&fonts, "double", "12:34", 80, 40).name, "double");
+    }
+
+    #[test]
+    fn falls_back_to_the_largest_font_that_fits() {
+        let fonts = builtin_fonts();
+        assert_eq!(pick_from(&fonts, "auto", "12:34", 80, 40).name, "double");
+        assert_eq!(pick_from(&fonts, "double", "12:34", 80, 6).name, "block");
+        assert_eq!(pick_from(&fonts, "block", "12:34", 20, 10).name, "small");
+        // nothing fits: the smallest font
+        assert_eq!(pick_from(&fonts, "block", "12:34", 5, 1).name, "braille");
+    }
+}
@@ -5,21 +5,18 @@
 
 use std::rc::Rc;
 
-use color_eyre::{Result, eyre::Error};
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use color_eyre::Result;
+use crossterm::event::{Event, KeyEvent, KeyEventKind};
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Style},
+    style::Style,
     text::Line,
-    widgets::{Block, BorderType, Borders, List, Paragraph},
+    widgets::{Block, Borders, Clear, List, Padding, Paragraph},
 };
 
 use crate::config::CONFIG;
 
-/// Type alias for the render function of a `MenuItem`.
-pub type FnRenderMenuItem<'a> = Box<dyn Fn(Rect) -> WiMenuItem<'a> + 'a>;
-
 /// Enum representing possible UI content types within a `MenuItem`.
 pub enum EnContentMenuItem<'a> {
     Paragraph(Paragraph<'a>),
@@ -34,25 +31,82 @@ pub struct WiMenuItem<'a> {
     pub show_overlay: bool,
 }
 
-/// Represents a single menu item in the content menu.
-pub struct StMenuItem<'a> {
-    pub title: String,
-    pub event: Box<dyn Fn(&Event) -> Result<(), Error>>,
-    pub starter: Box<dyn Fn() -> Result<(), Error>>,
-    pub render: FnRenderMenuItem<'a>,
+/// A panel that can be shown in the content menu.
+///
+/// Adding a panel means implementing this trait and passing a boxed instance to
+/// `ContentMenu::new`.
+pub trait MenuWidget {
+    /// Title shown in the navigation list.
+    fn title(&self) -> &str;
+
+    /// Handles an input event while the widget is selected.
+    fn handle_event(&mut self, event: &Event) -> Result<()>;
+
+    /// Called once when the menu is created, e.g. to spawn refresh threads.
+    fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Builds the main and overlay widgets for `area`.
+    fn render(&self, area: Rect) -> WiMenuItem<'static>;
+
+    /// Called every main loop tick, for every widget, selected or not.
+    fn tick(&mut self) {}
+
+    /// Whether the widget currently takes text input, so global keys are left to it.
+    fn captures_input(&self) -> bool {
+        false
+    }
+}
+
+/// Area of an overlay `width` x `height`, centred horizontally `top` rows below
+/// the top of `area` and shrunk to stay inside it.
+pub fn overlay_rect(area: Rect, width: u16, height: u16, top: u16) -> Rect {
+    let top = top.min(area.height);
+    let width = width.min(area.width);
+    let height = height.min(area.height - top);
+
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + top,
+        width,
+        height,
+    )
+}
+
+/// Block of an overlay: the theme's borders and colors, padded by a column on each side.
+pub fn overlay_block() -> Block<'static> {
+    let theme = &CONFIG().themes;
+    let borders = if theme.borders_on {
+        Borders::ALL
+    } else {
+        Borders::NONE
+    };
+
+    Block::default()
+        .borders(borders)
+        .border_type(theme.border_type)
+        .border_style(Style::default().fg(theme.border_color))
+        .padding(Padding {
+            left: 1,
+            right: 1,
+            top: 0,
+            bottom: 0,
+        })
+        .style(Style::default().bg(theme.bg_color).fg(theme.fg_color))
 }
 
 /// Main structure for managing and rendering a list of interactive menu items.
-pub struct ContentMenu<'a> {
+pub struct ContentMenu {
     selected_button: usize,
-    items: Vec<StMenuItem<'a>>,
+    items: Vec<Box<dyn MenuWidget>>,
 }
 
-impl<'a> ContentMenu<'a> {
-    /// Creates a new content menu and triggers each item's `starter` function.
-    pub fn new(items: Vec<StMenuItem<'a>>) -> Self {
-        for menu_item in &items {
-            let _ = (menu_item.starter)();
+impl ContentMenu {
+    /// Creates a new content menu and starts each item.
+    pub fn new(mut items: Vec<Box<dyn MenuWidget>>) -> Self {
+        for menu_item in items.iter_mut() {
+            let _ = menu_item.start();
         }
 
         Self {
@@ -61,15 +115,31 @@ impl<'a> ContentMenu<'a> {
         }
     }
 
+    /// Ticks every menu item.
+    pub fn tick(&mut self) {
+        for menu_item in self.items.iter_mut() {
+            menu_item.tick();
+        }
+    }
+
+    /// Whether the selected menu item currently takes text input.
+    pub fn captures_input(&self) -> bool {
+        self.items
+            .get(self.selected_button)
+            .is_some_and(|menu_item| menu_item.captures_input())
+    }
+
     /// Dispatches an input event to the currently selected menu item and handles key navigation.
     pub fn handle_events(&mut self, event: &Event) -> Result<()> {
-        if let Some(menu_item) = self.items.get(self.selected_button) {
-            (menu_item.event)(&event)?;
+        let captures_input = self.captures_input();
+
+        if let Some(menu_item) = self.items.get_mut(self.selected_button) {
+            menu_item.handle_event(event)?;
         }
 
         match event {
-            Event::Key(key_event) => {
-                self.handle_key_event(&key_event);
+            Event::Key(key_event) if !captures_input => {
+                self.handle_key_event(key_event);
                 Ok(())
             }
             _ => Ok(()),
@@ -79,7 +149,7 @@ impl<'a> ContentMenu<'a> {
     /// Renders the currently selected menu item and the navigation list on screen.
     pub fn render(&self, frame: &mut Frame, area: Rc<[Rect]>) {
         if let Some(menu_item) = self.items.get(self.selected_button) {
-            let widget = (menu_item.render)(area[0]);
+            let widget = menu_item.render(area[0]);
 
             match widget.content {
                 EnContentMenuItem::Paragraph(p) => frame.render_widget(p, area[0]),
@@ -87,9 +157,12 @@ impl<'a> ContentMenu<'a> {
             }
 
             if widget.show_overlay {
+                // `Clear` indexes the buffer directly and panics outside of it
+                let overlay_area = widget.overlay_area.intersection(area[0]);
+                frame.render_widget(Clear, overlay_area);
                 match widget.overlay {
-                    EnContentMenuItem::Paragraph(p) => frame.render_widget(p, widget.overlay_area),
-                    EnContentMenuItem::List(l) => frame.render_widget(l, widget.overlay_area),
+                    EnContentMenuItem::Paragraph(p) => frame.render_widget(p, overlay_area),
+                    EnContentMenuItem::List(l) => frame.render_widget(l, overlay_area),
                 }
             }
         }
@@ -103,7 +176,7 @@ impl<'a> ContentMenu<'a> {
             } else {
                 ratatui::style::Style::default()
             };
-            button_lines.push(Line::styled(item.title.clone(), style));
+            button_lines.push(Line::styled(item.title().to_string(), style));
         }
 
         let borders = if CONFIG().themes.borders_on {
@@ -153,3 +226,68 @@ impl<'a> ContentMenu<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::init_test_config;
+    use ratatui::{
+        Terminal,
+        backend::TestBackend,
+        layout::{Constraint, Direction, Layout},
+    };
+
+    /// A panel whose overlay reaches past the bottom and right of any small terminal.
+    struct OverflowingOverlay;
+
+    impl MenuWidget for OverflowingOverlay {
+        fn title(&self) -> &str {
+            "Overflow"
+        }
+
+        fn handle_event(&mut self, _event: &Event) -> Result<()> {
+            Ok(())
+        }
+
+        fn render(&self, area: Rect) -> WiMenuItem<'static> {
+            WiMenuItem {
+                content: EnContentMenuItem::Paragraph(Paragraph::new("content")),
+                overlay: EnContentMenuItem::Paragraph(
+                    Paragraph::new("overlay").block(Block::default().borders(Borders::ALL)),
+                ),
+                overlay_area: Rect::new(area.x + 2, area.y + 3, 200, 40),
+                show_overlay: true,
+            }
+        }
+    }
+
+    #[test]
+    fn overlay_rect_stays_inside_the_area() {
+        let area = Rect::new(10, 5, 40, 8);
+        assert_eq!(overlay_rect(area, 20, 4, 3), Rect::new(20, 8, 20, 4));
+        assert_eq!(overlay_rect(area, 60, 10, 3), Rect::new(10, 8, 40, 5));
+        assert_eq!(overlay_rect(area, 20, 4, 12), Rect::new(20, 13, 20, 0));
+        assert_eq!(overlay_rect(Rect::default(), 20, 4, 3), Rect::default());
+    }
+
+    #[test]
+    fn overlay_past_the_frame_is_clipped_to_the_panel() {
+        init_test_config();
+        let menu = ContentMenu::new(vec![Box::new(OverflowingOverlay)]);
+        let mut terminal = Terminal::new(TestBackend::new(80, 12)).unwrap();
+
+        terminal
+            .draw(|frame| {
+                let area = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(75), Constraint::Percentage(25)])
+                    .split(Rect::new(0, 6, 80, 6));
+                menu.render(frame, area);
+            })
+            .unwrap();
+
+        // the navigation list next to the panel is left alone
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(61, 7)].symbol(), "O");
+    }
+}
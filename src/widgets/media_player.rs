@@ -0,0 +1,429 @@
+use color_eyre::Result;
+use crossterm::event::{Event, KeyEvent, KeyEventKind};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, Padding, Paragraph},
+};
+use std::{
+    sync::{
+        Arc,
+        mpsc::{self, Receiver, Sender},
+    },
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    backend::media::{self, MediaBackend, PlaybackStatus, Player},
+    config::CONFIG,
+    widgets::{
+        content_menu::{EnContentMenuItem, MenuWidget, WiMenuItem},
+        timers::format_duration,
+    },
+};
+
+/// Refresh interval; between refreshes the position of playing tracks is
+/// advanced locally, as every refresh runs several busctl calls per player.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Seek step in microseconds, the unit MPRIS uses.
+const SEEK_STEP_US: i64 = 10_000_000;
+
+/// What the background threads send to the panel.
+enum MediaUpdate {
+    Players(Result<Vec<Player>>),
+    /// why a play/pause, next, previous or seek failed
+    CommandFailed(String),
+}
+
+/// Media panel: MPRIS players with their current track and playback controls.
+pub struct MediaPlayer {
+    players: Vec<Player>,
+    /// when `players` was read, to advance the positions of playing tracks
+    read_at: Instant,
+    selected: usize,
+    /// the poll loop and player commands both send here; `tick` applies it
+    refresh_rx: Receiver<MediaUpdate>,
+    refresh_tx: Sender<MediaUpdate>,
+    polling: bool,
+    backend: Arc<dyn MediaBackend>,
+    status: String,
+}
+
+impl MediaPlayer {
+    /// Create a panel using the backend selected by `AEX_MEDIA_BACKEND`.
+    pub fn new() -> Self {
+        Self::with_backend(media::from_env())
+    }
+
+    pub fn with_backend(backend: Arc<dyn MediaBackend>) -> Self {
+        let (refresh_tx, refresh_rx) = mpsc::channel();
+
+        Self {
+            players: Vec::new(),
+            read_at: Instant::now(),
+            selected: 0,
+            refresh_rx,
+            refresh_tx,
+            polling: false,
+            backend,
+            status: String::new(),
+        }
+    }
+
+    // ====== Input Handling ======
+
+    fn handle_key_event(&mut self, key_event: &KeyEvent) {
+        if key_event.kind != KeyEventKind::Press {
+            return;
+        }
+
+        let c = CONFIG();
+
+        if c.key_matches_with(
+            key_event,
+            &c.keybinds.content_left,
+            &c.keybinds.seek_modifier,
+        ) {
+            self.run_command(|backend, player| backend.seek(player, -SEEK_STEP_US));
+        } else if c.key_matches_with(
+            key_event,
+            &c.keybinds.content_right,
+            &c.keybinds.seek_modifier,
+        ) {
+            self.run_command(|backend, player| backend.seek(player, SEEK_STEP_US));
+        } else if c.key_matches(key_event, &c.keybinds.content_up) {
+            self.move_selected(self.players.len().saturating_sub(1));
+        } else if c.key_matches(key_event, &c.keybinds.content_down) {
+            self.move_selected(1);
+        } else if c.key_matches(key_event, &c.keybinds.content_left) {
+            self.run_command(|backend, player| backend.previous(player));
+        } else if c.key_matches(key_event, &c.keybinds.content_right) {
+            self.run_command(|backend, player| backend.next(player));
+        } else if c.key_matches(key_event, &c.keybinds.accept)
+            || c.key_matches(key_event, &c.keybinds.toggle)
+        {
+            self.run_command(|backend, player| backend.play_pause(player));
+        }
+    }
+
+    /// Move the selection by `step` players, wrapping around.
+    fn move_selected(&mut self, step: usize) {
+        if self.players.is_empty() {
+            return;
+        }
+        self.selected = (self.selected + step) % self.players.len();
+    }
+
+    fn apply_players(&mut self, players: Result<Vec<Player>>) {
+        match players {
+            Ok(players) => {
+                self.players = players;
+                self.read_at = Instant::now();
+                self.status.clear();
+            }
+            Err(e) => {
+                self.players.clear();
+                self.status = e.to_string();
+            }
+        }
+
+        self.selected = self.selected.min(self.players.len().saturating_sub(1));
+    }
+
+    // ====== Rendering UI Components ======
+
+    fn make_player_list(&self, max_width: usize) -> List<'static> {
+        let mut items: Vec<Line> = Vec::new();
+
+        for (i, player) in self.players.iter().enumerate() {
+            let is_selected = i == self.selected;
+            if i > 0 {
+                items.push(Line::from(""));
+            }
+
+            let style = if is_selected {
+                Style::default().fg(CONFIG().themes.content_selected_color)
+            } else {
+                Style::default().fg(CONFIG().themes.fg_color)
+            };
+
+            items.push(Line::from(Span::styled(
+                format!("{} {}", Self::status_symbol(player.status), player.identity),
+                style.add_modifier(Modifier::BOLD),
+            )));
+            items.push(Line::from(Span::styled(
+                format!("  {}", player.title.as_deref().unwrap_or("Nothing playing")),
+                style,
+            )));
+            if let Some(artist) = &player.artist {
+                items.push(Line::from(Span::styled(format!("  {}", artist), style)));
+            }
+            items.push(self.make_progress_line(player, is_selected, max_width));
+        }
+
+        if self.players.is_empty() && self.status.is_empty() {
+            items.push(Line::from("No media players running"));
+        }
+
+        if !self.status.is_empty() {
+            items.push(Line::from(Span::styled(
+                self.status.clone(),
+                Style::default().fg(Color::Red),
+            )));
+        }
+
+        let theme = &CONFIG().themes;
+
+        let block = Block::default()
+            .borders(if theme.borders_on {
+                Borders::ALL
+            } else {
+                Borders::NONE
+            })
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.border_color))
+            .padding(Padding {
+                left: 1,
+                right: 1,
+                top: 0,
+                bottom: 0,
+            });
+
+        List::new(items).block(block)
+    }
+
+    fn status_symbol(status: PlaybackStatus) -> &'static str {
+        match status {
+            PlaybackStatus::Playing => "▶",
+            PlaybackStatus::Paused => "‖",
+            PlaybackStatus::Stopped => "■",
+        }
+    }
+
+    /// Position of the track `elapsed` after the player was read; it only
+    /// moves while playing and stops at the end of the track.
+    fn current_position(player: &Player, elapsed: Duration) -> Duration {
+        let position = player.position.unwrap_or_default();
+        if player.status != PlaybackStatus::Playing {
+            return position;
+        }
+
+        let position = position + elapsed;
+        match player.length.filter(|length| !length.is_zero()) {
+            Some(length) => position.min(length),
+            None => position,
+        }
+    }
+
+    /// `[=====-----] 01:23 / 06:06`, or only the position when the length is unknown.
+    fn make_progress_line(
+        &self,
+        player: &Player,
+        is_selected: bool,
+        max_width: usize,
+    ) -> Line<'static> {
+        let position = Self::current_position(player, self.read_at.elapsed());
+        let Some(length) = player.length.filter(|length| !length.is_zero()) else {
+            return Line::from(format!("  {}", format_duration(position)));
+        };
+
+        let time = format!(
+            " {} / {}",
+            format_duration(position),
+            format_duration(length)
+        );
+        let bar_length = max_width.saturating_sub(time.len() + 4);
+        let filled_len = ((position.as_secs_f64() / length.as_secs_f64()) * bar_length as f64)
+            .clamp(0.0, bar_length as f64) as usize;
+
+        let theme = &CONFIG().themes;
+        let (side_color, filled_color, empty_color) = if is_selected {
+            (
+                theme.bar_side_color,
+                theme.bar_filled_color,
+                theme.bar_empty_color,
+            )
+        } else {
+            (
+                theme.bar_selected_side_color,
+                theme.bar_selected_filled_color,
+                theme.bar_selected_empty_color,
+            )
+        };
+
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled("[", Style::default().fg(side_color)),
+            Span::styled("=".repeat(filled_len), Style::default().fg(filled_color)),
+            Span::styled(
+                "-".repeat(bar_length - filled_len),
+                Style::default().fg(empty_color),
+            ),
+            Span::styled("]", Style::default().fg(side_color)),
+            Span::raw(time),
+        ])
+    }
+
+    fn make_empty_prompt(&self) -> (EnContentMenuItem<'static>, Rect) {
+        (
+            EnContentMenuItem::Paragraph(
+                Paragraph::new("").style(Style::default().bg(CONFIG().themes.bg_color)),
+            ),
+            Rect::default(),
+        )
+    }
+
+    // ====== Player Commands ======
+
+    /// Run a command on the selected player on a background thread, as a
+    /// player that hangs keeps busctl waiting; `tick` picks up the players
+    /// read again afterwards, or why the command failed.
+    fn run_command(
+        &mut self,
+        command: impl FnOnce(&dyn MediaBackend, &str) -> Result<()> + Send + 'static,
+    ) {
+        let Some(player) = self.players.get(self.selected) else {
+            return;
+        };
+
+        let bus_name = player.bus_name.clone();
+        let backend = self.backend.clone();
+        let tx = self.refresh_tx.clone();
+
+        std::thread::spawn(move || {
+            let update = match command(backend.as_ref(), &bus_name) {
+                Ok(()) => MediaUpdate::Players(backend.players()),
+                Err(e) => MediaUpdate::CommandFailed(e.to_string()),
+            };
+            let _ = tx.send(update);
+        });
+    }
+}
+
+impl MenuWidget for MediaPlayer {
+    fn title(&self) -> &str {
+        "Media"
+    }
+
+    fn handle_event(&mut self, event: &Event) -> Result<()> {
+        match event {
+            Event::Key(key_event) => {
+                self.handle_key_event(key_event);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Start background thread to re-read the players every `POLL_INTERVAL`.
+    fn start(&mut self) -> Result<()> {
+        if self.polling {
+            return Ok(());
+        }
+        self.polling = true;
+
+        let tx = self.refresh_tx.clone();
+        let backend = self.backend.clone();
+        std::thread::spawn(move || {
+            while tx.send(MediaUpdate::Players(backend.players())).is_ok() {
+                sleep(POLL_INTERVAL);
+            }
+        });
+
+        Ok(())
+    }
+
+    fn render(&self, area: Rect) -> WiMenuItem<'static> {
+        let max_width = area.width.saturating_sub(4) as usize;
+        let (overlay, overlay_area) = self.make_empty_prompt();
+
+        WiMenuItem {
+            content: EnContentMenuItem::List(self.make_player_list(max_width)),
+            overlay,
+            overlay_area,
+            show_overlay: false,
+        }
+    }
+
+    /// Apply the players and command failures sent by the background threads;
+    /// the redraw after each tick moves the positions in between.
+    fn tick(&mut self) {
+        let updates: Vec<MediaUpdate> = self.refresh_rx.try_iter().collect();
+
+        for update in updates {
+            match update {
+                MediaUpdate::Players(players) => self.apply_players(players),
+                MediaUpdate::CommandFailed(error) => self.status = error,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::media::FakeMediaBackend;
+    use color_eyre::eyre::eyre;
+
+    fn player(status: PlaybackStatus, position: u64, length: Option<u64>) -> Player {
+        Player {
+            bus_name: "org.mpris.MediaPlayer2.test".to_string(),
+            identity: "Test".to_string(),
+            status,
+            title: None,
+            artist: None,
+            position: Some(Duration::from_secs(position)),
+            length: length.map(Duration::from_secs),
+        }
+    }
+
+    #[test]
+    fn position_advances_only_while_playing() {
+        let elapsed = Duration::from_secs(3);
+        let position = |player| MediaPlayer::current_position(&player, elapsed).as_secs();
+
+        assert_eq!(position(player(PlaybackStatus::Playing, 10, Some(60))), 13);
+        assert_eq!(position(player(PlaybackStatus::Paused, 10, Some(60))), 10);
+        assert_eq!(position(player(PlaybackStatus::Stopped, 0, None)), 0);
+        // a live stream has no length to stop at
+        assert_eq!(position(player(PlaybackStatus::Playing, 10, None)), 13);
+    }
+
+    #[test]
+    fn position_stops_at_the_end_of_the_track() {
+        let player = player(PlaybackStatus::Playing, 58, Some(60));
+        let position = MediaPlayer::current_position(&player, Duration::from_secs(5));
+        assert_eq!(position, Duration::from_secs(60));
+    }
+
+    /// Tick until a background command has sent its update.
+    fn tick_until(media: &mut MediaPlayer, done: impl Fn(&MediaPlayer) -> bool) {
+        for _ in 0..100 {
+            media.tick();
+            if done(media) {
+                return;
+            }
+            sleep(Duration::from_millis(10));
+        }
+        panic!("no update arrived");
+    }
+
+    #[test]
+    fn commands_run_in_the_background_and_report_back() {
+        let backend = Arc::new(FakeMediaBackend::default());
+        let mut media = MediaPlayer::with_backend(backend.clone());
+        media.apply_players(backend.players());
+        let status = media.players[0].status;
+
+        media.run_command(|backend, player| backend.play_pause(player));
+        tick_until(&mut media, |media| media.players[0].status != status);
+
+        media.run_command(|_, player| Err(eyre!("{} did not answer", player)));
+        tick_until(&mut media, |media| !media.status.is_empty());
+        assert!(media.status.ends_with("did not answer"));
+        assert!(!media.players.is_empty());
+    }
+}
@@ -1,4 +1,12 @@
+pub mod alarms;
 pub mod audio_mixer;
+pub mod calendar;
 pub mod clock;
+pub mod clock_font;
 pub mod content_menu;
+pub mod media_player;
 pub mod net_connect;
+pub mod pomodoro;
+pub mod text_form;
+pub mod timers;
+pub mod world_clock;
@@ -1,41 +1,153 @@
-use color_eyre::Result;
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use color_eyre::{Result, eyre::eyre};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, List, Padding, Paragraph},
+    widgets::{Block, Borders, List, Padding, Paragraph},
 };
 use std::{
-    collections::HashSet,
-    process::Command,
-    sync::{Arc, Mutex},
+    sync::{
+        Arc,
+        mpsc::{self, Receiver},
+    },
     thread::sleep,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use crate::{
+    backend::network::{
+        self, NetworkBackend, SavedConnection, SsidCache, WifiNetwork, WifiSecurity,
+    },
     config::CONFIG,
-    widgets::content_menu::{EnContentMenuItem, WiMenuItem},
+    widgets::{
+        content_menu::{EnContentMenuItem, MenuWidget, WiMenuItem, overlay_block, overlay_rect},
+        text_form::{FormAction, FormField, TextForm},
+    },
 };
 
+/// State gathered by the refresh thread.
+struct NetRefresh {
+    wifi_list: Result<Vec<WifiNetwork>>,
+    saved: Result<Vec<SavedConnection>>,
+    radio_on: bool,
+    connected_ssid: String,
+    connection_info: Vec<String>,
+}
+
+const SPINNER: [&str; 4] = ["|", "/", "-", "\\"];
+
+/// Which list the panel shows.
+#[derive(Clone, Copy, PartialEq)]
+enum NetView {
+    Networks,
+    Saved,
+}
+
+/// Overlay for changing a saved connection.
+enum SavedPrompt {
+    /// new priority being typed
+    Priority {
+        uuid: String,
+        name: String,
+        input: String,
+    },
+    Forget {
+        uuid: String,
+        name: String,
+    },
+}
+
+/// How to connect to a network.
+enum ConnectRequest {
+    Password(String),
+    Open,
+    /// UUID of the saved connection
+    Saved(String),
+    Hidden(WifiSecurity, String),
+}
+
+/// The open password or hidden network form.
+struct ConnectForm {
+    /// network the password is for; `None` for the hidden network form
+    ssid: Option<String>,
+    form: TextForm,
+}
+
+/// A connect running on a background thread.
+struct Connecting {
+    ssid: String,
+    started: Instant,
+    result_rx: Receiver<Result<()>>,
+}
+
+/// A rescan running on a background thread; it sends the networks found.
+struct Scanning {
+    started: Instant,
+    result_rx: Receiver<Result<Vec<WifiNetwork>>>,
+}
+
+/// A disconnect, radio toggle or saved connection change running on a
+/// background thread.
+struct PendingAction {
+    action: WifiAction,
+    result_rx: Receiver<Result<ActionResult>>,
+}
+
+enum WifiAction {
+    /// disconnect from the network with this SSID, empty when unknown
+    Disconnect(String),
+    /// turn the radio on or off
+    Radio(bool),
+    /// forget a saved connection or change its autoconnect or priority
+    ChangeSaved,
+}
+
+enum ActionResult {
+    Done,
+    /// the saved connections read again after a change
+    Saved(Vec<SavedConnection>),
+}
+
+/// Outcome of the last connect, disconnect, radio toggle or rescan, shown
+/// under the network list.
+enum ActionOutcome {
+    Succeeded(String),
+    /// message with nmcli's reason
+    Failed(String),
+}
+
 /// Manages WiFi connectivity UI, including:
 /// - Listing available networks
 /// - Handling password prompts and connection requests
 /// - Displaying connected network
 /// - Periodic auto-refresh of network list
-#[derive(Clone)]
 pub struct NetConnect {
     selected_ssid: usize,
-    wifi_list: Vec<(String, u8)>,
-    started_refresh: bool,
+    wifi_list: Vec<WifiNetwork>,
+    refresh_rx: Option<Receiver<NetRefresh>>,
     connected_ssid: String,
-    show_prompt: bool,
-    prompt_ssid: String,
-    prompt_pass: String,
+    connect_form: Option<ConnectForm>,
     show_info: bool,
     connection_info: Vec<String>,
     scroll_offset: usize,
+    connecting: Option<Connecting>,
+    outcome: Option<ActionOutcome>,
+    scanning: Option<Scanning>,
+    pending_action: Option<PendingAction>,
+    radio_on: bool,
+    /// no refresh has arrived yet
+    loading: bool,
+    view: NetView,
+    saved: Vec<SavedConnection>,
+    selected_saved: usize,
+    saved_prompt: Option<SavedPrompt>,
+    /// why the saved connections could not be read or changed
+    saved_status: String,
+    /// shared with the refresh and rescan threads
+    ssids: Arc<SsidCache>,
+    backend: Arc<dyn NetworkBackend>,
+    status: String,
 }
 
 impl NetConnect {
@@ -43,79 +155,36 @@ impl NetConnect {
 
     /// Create a new `NetConnect` instance with default state.
     pub fn new() -> Self {
+        Self::with_backend(network::from_env())
+    }
+
+    pub fn with_backend(backend: Arc<dyn NetworkBackend>) -> Self {
         Self {
             selected_ssid: 0,
             wifi_list: Vec::new(),
-            started_refresh: false,
+            refresh_rx: None,
             connected_ssid: String::new(),
-            show_prompt: false,
-            prompt_ssid: String::new(),
-            prompt_pass: String::new(),
+            connect_form: None,
             show_info: false,
             connection_info: Vec::new(),
             scroll_offset: 0,
+            connecting: None,
+            outcome: None,
+            scanning: None,
+            pending_action: None,
+            radio_on: true,
+            loading: true,
+            view: NetView::Networks,
+            saved: Vec::new(),
+            selected_saved: 0,
+            saved_prompt: None,
+            saved_status: String::new(),
+            ssids: Arc::default(),
+            backend,
+            status: String::new(),
         }
     }
 
-    // ====== Public Interface Methods ======
-
-    /// Handle input events (keys only).
-    pub fn handle_events(&mut self, event: &Event) -> Result<()> {
-        match event {
-            Event::Key(key_event) => {
-                self.handle_key_event(&key_event);
-                Ok(())
-            }
-            _ => Ok(()),
-        }
-    }
-
-    /// Build the main and overlay widgets for rendering.
-    pub fn get_widget(&self, area: Rect) -> WiMenuItem<'static> {
-        let max_width = (area.width - 4) as usize;
-
-        let list = self.make_wifi_widget_list(max_width);
-
-        let (overlay, overlay_area) = if self.show_prompt {
-            self.make_prompt(max_width, area)
-        } else if self.show_info {
-            self.make_info_overlay(max_width, area)
-        } else {
-            self.make_empty_prompt()
-        };
-
-        WiMenuItem {
-            content: EnContentMenuItem::List(list),
-            overlay,
-            overlay_area,
-            show_overlay: self.show_prompt || self.show_info,
-        }
-    }
-
-    /// Start background thread to refresh network list and connection state.
-    pub fn start_auto_refresh(this: Arc<Mutex<Self>>) {
-        {
-            let mut guard = this.lock().unwrap();
-            if guard.started_refresh {
-                return;
-            }
-            guard.started_refresh = true;
-        };
-
-        std::thread::spawn(move || {
-            loop {
-                let new_list = NetConnect::make_wifi_list();
-
-                if let Ok(mut nc) = this.lock() {
-                    nc.connected_ssid = nc.get_connected_ssid();
-                    nc.connection_info = nc.get_connection_info();
-                    nc.wifi_list = new_list;
-                }
-                sleep(Duration::from_secs(3));
-            }
-        });
-    }
-
     // ====== Input Handling ======
 
     fn handle_key_event(&mut self, key_event: &KeyEvent) {
@@ -125,17 +194,21 @@ impl NetConnect {
 
         let c = CONFIG();
 
-        if self.show_prompt {
-            if c.key_matches(key_event, &c.keybinds.accept) {
-                self.accept_connect();
-            } else if c.key_matches(key_event, &c.keybinds.cancel)
-                || c.key_matches(key_event, &c.keybinds.info)
-            {
-                self.cancel_connect();
-            } else if key_event.code == KeyCode::Backspace {
-                self.prompt_pass.pop();
-            } else if let KeyCode::Char(c) = key_event.code {
-                self.prompt_pass.push(c);
+        // keys wait until the running connect has finished
+        if self.connecting.is_some() {
+            return;
+        }
+
+        if self.saved_prompt.is_some() {
+            self.handle_saved_prompt_key(key_event);
+            return;
+        }
+
+        if let Some(connect_form) = self.connect_form.as_mut() {
+            match connect_form.form.handle_key(key_event) {
+                FormAction::Submit => self.accept_connect(),
+                FormAction::Cancel => self.connect_form = None,
+                FormAction::Editing => {}
             }
 
             return;
@@ -156,31 +229,113 @@ impl NetConnect {
             return;
         }
 
+        if c.key_matches(key_event, &c.keybinds.content_left)
+            || c.key_matches(key_event, &c.keybinds.content_right)
+        {
+            self.view = match self.view {
+                NetView::Networks => NetView::Saved,
+                NetView::Saved => NetView::Networks,
+            };
+            return;
+        }
+
+        if self.view == NetView::Saved {
+            self.handle_saved_key(key_event);
+            return;
+        }
+
         if c.key_matches(key_event, &c.keybinds.content_up) {
             self.move_selected_up();
         } else if c.key_matches(key_event, &c.keybinds.content_down) {
             self.move_selected_down();
         } else if c.key_matches(key_event, &c.keybinds.accept) {
-            self.open_prompt();
+            self.connect_selected();
         } else if c.key_matches(key_event, &c.keybinds.info) {
             self.open_info();
+        } else if c.key_matches(key_event, &c.keybinds.disconnect) {
+            self.disconnect();
+        } else if c.key_matches(key_event, &c.keybinds.radio) {
+            self.toggle_radio();
+        } else if c.key_matches(key_event, &c.keybinds.rescan) {
+            self.start_rescan();
+        }
+    }
+
+    fn handle_saved_key(&mut self, key_event: &KeyEvent) {
+        let c = CONFIG();
+        let count = self.saved.len();
+
+        if c.key_matches(key_event, &c.keybinds.content_up) && count > 0 {
+            self.selected_saved = (self.selected_saved + count - 1) % count;
+        } else if c.key_matches(key_event, &c.keybinds.content_down) && count > 0 {
+            self.selected_saved = (self.selected_saved + 1) % count;
+        } else if c.key_matches(key_event, &c.keybinds.toggle) {
+            self.toggle_autoconnect();
+        } else if c.key_matches(key_event, &c.keybinds.delete) {
+            self.saved_prompt =
+                self.saved
+                    .get(self.selected_saved)
+                    .map(|connection| SavedPrompt::Forget {
+                        uuid: connection.uuid.clone(),
+                        name: connection.name.clone(),
+                    });
+        } else if c.key_matches(key_event, &c.keybinds.accept) {
+            self.saved_prompt =
+                self.saved
+                    .get(self.selected_saved)
+                    .map(|connection| SavedPrompt::Priority {
+                        uuid: connection.uuid.clone(),
+                        name: connection.name.clone(),
+                        input: connection.priority.to_string(),
+                    });
+        }
+    }
+
+    fn handle_saved_prompt_key(&mut self, key_event: &KeyEvent) {
+        let c = CONFIG();
+
+        if c.key_matches(key_event, &c.keybinds.cancel) {
+            self.saved_prompt = None;
+        } else if c.key_matches(key_event, &c.keybinds.accept) {
+            self.accept_saved_prompt();
+        } else if let Some(SavedPrompt::Priority { input, .. }) = self.saved_prompt.as_mut() {
+            match key_event.code {
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(ch) if ch.is_ascii_digit() || (ch == '-' && input.is_empty()) => {
+                    input.push(ch)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Rows of the network list; the last one is "+ Hidden network".
+    fn network_rows(&self) -> usize {
+        if self.radio_on {
+            self.wifi_list.len() + 1
+        } else {
+            0
         }
     }
 
     fn move_selected_down(&mut self) {
-        self.selected_ssid += 1;
-        if self.selected_ssid >= self.wifi_list.len() {
-            self.selected_ssid = 0;
+        let rows = self.network_rows();
+        if rows == 0 {
+            return;
         }
+
+        self.selected_ssid = (self.selected_ssid + 1) % rows;
     }
 
     fn move_selected_up(&mut self) {
-        let number = self.selected_ssid as i32 - 1;
-        self.selected_ssid = self.selected_ssid.saturating_sub(1);
-
-        if number < 0 {
-            self.selected_ssid = self.wifi_list.len() - 1;
+        let rows = self.network_rows();
+        if rows == 0 {
+            return;
         }
+
+        self.selected_ssid = (self.selected_ssid + rows - 1) % rows;
     }
 
     fn move_scrollbar_up(&mut self) {
@@ -205,33 +360,271 @@ impl NetConnect {
         self.show_info = true;
     }
 
+    /// Connect right away to saved and open networks, ask for a password otherwise.
+    fn connect_selected(&mut self) {
+        if self.radio_on && self.selected_ssid == self.wifi_list.len() {
+            self.open_hidden_form();
+            return;
+        }
+
+        let Some(network) = self.wifi_list.get(self.selected_ssid) else {
+            return;
+        };
+
+        let ssid = network.ssid.clone();
+        if let Some(uuid) = &network.uuid {
+            self.start_connect(ssid, ConnectRequest::Saved(uuid.clone()));
+        } else if network.is_open() {
+            self.start_connect(ssid, ConnectRequest::Open);
+        } else {
+            self.open_prompt();
+        }
+    }
+
     fn open_prompt(&mut self) {
-        self.show_prompt = true;
-        self.prompt_ssid = self
+        let ssid = self
             .wifi_list
             .get(self.selected_ssid)
-            .map(|(ssid, _)| ssid.clone())
+            .map(|network| network.ssid.clone())
             .unwrap_or_default();
-        self.prompt_pass.clear();
+
+        self.connect_form = Some(ConnectForm {
+            form: TextForm::new(
+                format!("Password for {}", ssid),
+                "connect",
+                vec![FormField::secret("Password")],
+            ),
+            ssid: Some(ssid),
+        });
     }
 
-    fn accept_connect(&mut self) {
-        self.connect_to_wifi(&self.prompt_ssid, &self.prompt_pass);
-        self.show_prompt = false;
-        self.prompt_ssid.clear();
-        self.prompt_pass.clear();
+    fn open_hidden_form(&mut self) {
+        self.connect_form = Some(ConnectForm {
+            ssid: None,
+            form: TextForm::new(
+                "Hidden network",
+                "connect",
+                vec![
+                    FormField::text("SSID"),
+                    FormField::choice("Security", &WifiSecurity::LABELS),
+                    FormField::secret("Password"),
+                ],
+            ),
+        });
     }
 
-    fn cancel_connect(&mut self) {
-        self.show_prompt = false;
-        self.prompt_ssid.clear();
-        self.prompt_pass.clear();
+    fn accept_connect(&mut self) {
+        let Some(connect_form) = self.connect_form.as_mut() else {
+            return;
+        };
+        let form = &mut connect_form.form;
+
+        let (ssid, request) = match &connect_form.ssid {
+            Some(ssid) => {
+                let password = form.value(0);
+                if password.is_empty() {
+                    form.error = Some("Enter a password".to_string());
+                    return;
+                }
+                (ssid.clone(), ConnectRequest::Password(password.to_string()))
+            }
+            None => {
+                let ssid = form.value(0);
+                let security = WifiSecurity::from_label(form.value(1));
+                let password = form.value(2);
+                if ssid.is_empty() {
+                    form.error = Some("Enter the SSID".to_string());
+                    return;
+                }
+                if security != WifiSecurity::Open && password.is_empty() {
+                    form.error = Some("Enter a password or pick None".to_string());
+                    return;
+                }
+                (
+                    ssid.to_string(),
+                    ConnectRequest::Hidden(security, password.to_string()),
+                )
+            }
+        };
+
+        self.connect_form = None;
+        self.start_connect(ssid, request);
     }
 
     // ====== Rendering UI Components ======
 
+    /// `Networks  Saved`, with the shown view highlighted.
+    fn make_view_tabs_line(&self) -> Line<'static> {
+        let theme = &CONFIG().themes;
+        let tab = |title: &'static str, view: NetView| {
+            if self.view == view {
+                Span::styled(
+                    title,
+                    Style::default()
+                        .fg(theme.content_selected_color)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                Span::styled(title, Style::default().fg(Color::DarkGray))
+            }
+        };
+
+        Line::from(vec![
+            tab("Networks", NetView::Networks),
+            Span::raw("  "),
+            tab("Saved", NetView::Saved),
+        ])
+    }
+
+    fn make_list_block(&self) -> Block<'static> {
+        let borders = if CONFIG().themes.borders_on {
+            Borders::ALL
+        } else {
+            Borders::NONE
+        };
+
+        Block::default()
+            .borders(borders)
+            .border_type(CONFIG().themes.border_type)
+            .border_style(Style::default().fg(CONFIG().themes.border_color))
+            .padding(Padding {
+                left: 1,
+                right: 1,
+                top: 0,
+                bottom: 0,
+            })
+    }
+
+    /// The saved connections; when they do not fit in `height` lines, only the
+    /// rows around `selected_saved` are shown.
+    fn make_saved_widget_list(&self, max_width: usize, height: usize) -> List<'static> {
+        let mut items: Vec<Line> = vec![self.make_view_tabs_line(), Line::from("")];
+
+        // name | type | last used | autoconnect | priority
+        let name_width = max_width.saturating_sub(33).max(8);
+        items.push(Line::from(Span::styled(
+            format!(
+                "{:<name_width$} {:<8} {:<10} {:<6} {:>4}",
+                "Name", "Type", "Last used", "Auto", "Prio"
+            ),
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )));
+
+        let changing = matches!(
+            self.pending_action,
+            Some(PendingAction {
+                action: WifiAction::ChangeSaved,
+                ..
+            })
+        );
+        let status_lines: Vec<String> = if changing {
+            vec!["Saving...".to_string()]
+        } else if self.saved_status.is_empty() {
+            Vec::new()
+        } else {
+            wrap_text(&self.saved_status, max_width)
+        };
+
+        // tabs, spacer and header above; a spacer and the status below
+        let below = if status_lines.is_empty() {
+            0
+        } else {
+            status_lines.len() + 1
+        };
+        let shown_rows = height.saturating_sub(items.len() + below).max(1);
+        let first_shown = self
+            .selected_saved
+            .saturating_sub(shown_rows / 2)
+            .min(self.saved.len().saturating_sub(shown_rows));
+
+        for (i, connection) in self
+            .saved
+            .iter()
+            .enumerate()
+            .skip(first_shown)
+            .take(shown_rows)
+        {
+            let line = Line::from(self.format_saved_connection(connection, name_width));
+            items.push(if i == self.selected_saved {
+                line.style(CONFIG().themes.content_selected_color)
+            } else {
+                line.style(CONFIG().themes.fg_color)
+            });
+        }
+
+        if self.saved.is_empty() && self.saved_status.is_empty() {
+            items.push(Line::from("No saved connections"));
+        }
+
+        if !status_lines.is_empty() {
+            items.push(Line::from(""));
+            for line in status_lines {
+                items.push(Line::from(Span::styled(
+                    line,
+                    Style::default().fg(Color::Red),
+                )));
+            }
+        }
+
+        List::new(items).block(self.make_list_block())
+    }
+
+    fn format_saved_connection(&self, connection: &SavedConnection, name_width: usize) -> String {
+        let name = if connection.name.chars().count() > name_width {
+            let short: String = connection.name.chars().take(name_width - 1).collect();
+            format!("{}…", short)
+        } else {
+            connection.name.clone()
+        };
+        let conn_type = match connection.conn_type.as_str() {
+            "802-11-wireless" => "wifi",
+            "802-3-ethernet" => "ethernet",
+            other => other,
+        };
+        let last_used = connection
+            .last_used
+            .map(|time| time.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "never".to_string());
+        let autoconnect = if connection.autoconnect {
+            "auto"
+        } else {
+            "manual"
+        };
+
+        format!(
+            "{:<name_width$} {:<8} {:<10} {:<6} {:>4}",
+            name, conn_type, last_used, autoconnect, connection.priority
+        )
+    }
+
+    fn make_saved_prompt(
+        &self,
+        prompt: &SavedPrompt,
+        max_width: usize,
+        area: Rect,
+    ) -> (EnContentMenuItem<'static>, Rect) {
+        let (title, line) = match prompt {
+            SavedPrompt::Priority { name, input, .. } => {
+                (format!("Priority of {}", name), format!("{}█", input))
+            }
+            SavedPrompt::Forget { name, .. } => (
+                format!("Forget {}?", name),
+                "accept forgets it, cancel keeps it".to_string(),
+            ),
+        };
+
+        let paragraph = Paragraph::new(Line::from(line)).block(overlay_block().title(title));
+
+        (
+            EnContentMenuItem::Paragraph(paragraph),
+            overlay_rect(area, max_width.saturating_sub(2) as u16, 3, 3),
+        )
+    }
+
     fn make_wifi_widget_list(&self, max_width: usize) -> List<'static> {
-        let mut items: Vec<Line> = Vec::new();
+        let mut items: Vec<Line> = vec![self.make_view_tabs_line(), Line::from("")];
 
         let connected_line = format!("Connected to: {}", self.connected_ssid);
         items.push(Line::from(Span::styled(
@@ -241,14 +634,21 @@ impl NetConnect {
 
         items.push(Line::from(" ".repeat(max_width)));
 
+        if let Some(notice) = self.make_wifi_notice() {
+            items.push(Line::from(Span::styled(
+                notice,
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
         let mut wifi_lines: Vec<Line> = self
             .wifi_list
             .iter()
             .enumerate()
-            .map(|(i, (ssid, signal))| {
-                let line = self.make_wifi_line(ssid, *signal, max_width);
+            .map(|(i, network)| {
+                let line = self.make_wifi_line(network, max_width);
 
-                if i == self.selected_ssid && !self.show_prompt {
+                if i == self.selected_ssid && self.connect_form.is_none() {
                     line.style(CONFIG().themes.content_selected_color)
                 } else {
                     line.style(CONFIG().themes.fg_color)
@@ -258,60 +658,92 @@ impl NetConnect {
 
         items.append(&mut wifi_lines);
 
-        let borders = if CONFIG().themes.borders_on {
-            Borders::ALL
-        } else {
-            Borders::NONE
-        };
+        if self.radio_on {
+            let style = if self.selected_ssid == self.wifi_list.len() {
+                CONFIG().themes.content_selected_color
+            } else {
+                Color::DarkGray
+            };
+            items.push(Line::from(Span::styled(
+                "+ Hidden network",
+                Style::default().fg(style),
+            )));
+        }
 
-        List::new(items).block(
-            Block::default()
-                .borders(borders)
-                .border_type(CONFIG().themes.border_type)
-                .border_style(Style::default().fg(CONFIG().themes.border_color))
-                .padding(Padding {
-                    left: 1,
-                    right: 1,
-                    top: 0,
-                    bottom: 0,
-                }),
-        )
-    }
+        if let Some(outcome) = &self.outcome {
+            let (text, color) = match outcome {
+                ActionOutcome::Succeeded(text) => (text, Color::Green),
+                ActionOutcome::Failed(text) => (text, Color::Red),
+            };
 
-    fn make_prompt(&self, max_width: usize, area: Rect) -> (EnContentMenuItem<'static>, Rect) {
-        let prompt_lines = self.make_prompt_lines(max_width + 1);
+            items.push(Line::from(""));
+            for line in wrap_text(text, max_width) {
+                items.push(Line::from(Span::styled(line, Style::default().fg(color))));
+            }
+        }
 
-        let borders = if CONFIG().themes.borders_on {
-            Borders::ALL
+        if !self.status.is_empty() {
+            items.push(Line::from(Span::styled(
+                self.status.clone(),
+                Style::default().fg(Color::Red),
+            )));
+        }
+
+        List::new(items).block(self.make_list_block())
+    }
+
+    /// Why the network list is empty or may change: a disconnect or radio
+    /// toggle is running, the radio is off, a scan is running or nothing was found.
+    fn make_wifi_notice(&self) -> Option<String> {
+        let keybinds = &CONFIG().keybinds;
+
+        if let Some(pending) = &self.pending_action {
+            Some(match pending.action {
+                WifiAction::Disconnect(_) => "Disconnecting...".to_string(),
+                WifiAction::Radio(true) => "Turning wifi on...".to_string(),
+                WifiAction::Radio(false) => "Turning wifi off...".to_string(),
+                WifiAction::ChangeSaved => return None,
+            })
+        } else if !self.radio_on {
+            Some(format!(
+                "Wifi is off, press {} to turn it on",
+                keybinds.radio
+            ))
+        } else if let Some(scanning) = &self.scanning {
+            // the panel redraws once a second, so the spinner turns once a second
+            let frame = scanning.started.elapsed().as_secs() as usize % SPINNER.len();
+            Some(format!("Scanning {}", SPINNER[frame]))
+        } else if self.loading {
+            Some("Scanning...".to_string())
+        } else if self.wifi_list.is_empty() && self.status.is_empty() {
+            Some(format!(
+                "No networks found, press {} to scan again",
+                keybinds.rescan
+            ))
         } else {
-            Borders::NONE
-        };
+            None
+        }
+    }
 
-        let paragraph = Paragraph::new(prompt_lines)
-            .style(Style::default().bg(Color::Black))
-            .block(
-                Block::default()
-                    .title("Password")
-                    .borders(borders)
-                    .border_type(CONFIG().themes.border_type)
-                    .border_style(Style::default().fg(CONFIG().themes.border_color))
-                    .style(
-                        Style::default()
-                            .bg(CONFIG().themes.bg_color)
-                            .fg(CONFIG().themes.fg_color),
-                    ),
-            );
-
-        let prompt_width = (max_width - 2) as u16;
-        let prompt_height = 3;
-        let w = prompt_width.min(area.width);
-        let h = prompt_height.min(area.height);
-
-        let x = area.x + (area.width - w) / 2;
-        let y = area.y + 3;
-        let rect = Rect::new(x, y, w, h);
-
-        (EnContentMenuItem::Paragraph(paragraph), rect)
+    /// `Connecting to X... |` with a spinner that turns on every redraw second.
+    fn make_connecting_overlay(
+        &self,
+        connecting: &Connecting,
+        max_width: usize,
+        area: Rect,
+    ) -> (EnContentMenuItem<'static>, Rect) {
+        let frame = connecting.started.elapsed().as_secs() as usize % SPINNER.len();
+
+        let paragraph = Paragraph::new(Line::from(format!(
+            "Connecting to {}... {}",
+            connecting.ssid, SPINNER[frame]
+        )))
+        .block(overlay_block());
+
+        (
+            EnContentMenuItem::Paragraph(paragraph),
+            overlay_rect(area, max_width.saturating_sub(2) as u16, 3, 3),
+        )
     }
 
     fn make_empty_prompt(&self) -> (EnContentMenuItem<'static>, Rect) {
@@ -323,27 +755,6 @@ impl NetConnect {
         )
     }
 
-    fn make_prompt_lines(&self, max_width: usize) -> Vec<Line<'static>> {
-        let mut lines = Vec::new();
-
-        let masked_pass = "*".repeat(self.prompt_pass.len());
-        let pass_line = format!("{:<width$}", masked_pass, width = max_width);
-        lines.push(Line::from(Span::styled(
-            pass_line,
-            Style::default().bg(CONFIG().themes.bg_color),
-        )));
-
-        let prompt_height = 3;
-        while lines.len() < prompt_height as usize {
-            lines.push(Line::from(Span::styled(
-                " ".repeat(38),
-                Style::default().bg(CONFIG().themes.bg_color),
-            )));
-        }
-
-        lines
-    }
-
     fn make_info_overlay(
         &self,
         max_width: usize,
@@ -416,46 +827,25 @@ impl NetConnect {
                 .add_modifier(Modifier::BOLD),
         )));
 
-        let borders = if CONFIG().themes.borders_on {
-            Borders::ALL
-        } else {
-            Borders::NONE
-        };
-
-        let paragraph = Paragraph::new(lines)
-            .style(
-                Style::default()
-                    .bg(CONFIG().themes.bg_color)
-                    .fg(CONFIG().themes.fg_color),
-            )
-            .block(
-                Block::default()
-                    .padding(Padding {
-                        left: 1,
-                        right: 1,
-                        top: 0,
-                        bottom: 0,
-                    })
-                    .borders(borders)
-                    .border_type(CONFIG().themes.border_type)
-                    .border_style(Style::default().fg(CONFIG().themes.border_color)),
-            );
-
-        let w = (max_width + 4) as u16;
-        let h = height as u16;
-
-        let x = area.x + (area.width - w) / 2;
-        let y = area.y;
+        let paragraph = Paragraph::new(lines).block(overlay_block());
 
         (
             EnContentMenuItem::Paragraph(paragraph),
-            Rect::new(x, y, w, h),
+            overlay_rect(area, (max_width + 4) as u16, height as u16, 0),
         )
     }
 
-    fn make_wifi_line(&self, ssid: &String, signal: u8, max_width: usize) -> Line<'static> {
-        let formatted_ssid = self.format_ssid_string(ssid, max_width.saturating_sub(7));
-        let formatted_signal = self.format_signal(signal);
+    /// `ssid ... saved ▃▅▇`; saved and open networks connect without a password.
+    fn make_wifi_line(&self, network: &WifiNetwork, max_width: usize) -> Line<'static> {
+        let formatted_ssid = self.format_ssid_string(&network.ssid, max_width.saturating_sub(13));
+        let tag = if network.is_known() {
+            "saved "
+        } else if network.is_open() {
+            "open "
+        } else {
+            ""
+        };
+        let formatted_signal = format!("{}{}", tag, self.format_signal(network.signal));
 
         let ssid_width = formatted_ssid.chars().count();
         let signal_width = formatted_signal.chars().count();
@@ -476,12 +866,12 @@ impl NetConnect {
         Line::from(vec![Span::raw(display)])
     }
 
-    fn format_ssid_string(&self, ssid: &String, max_ssid_len: usize) -> String {
+    fn format_ssid_string(&self, ssid: &str, max_ssid_len: usize) -> String {
         let is_long = ssid.chars().count() > max_ssid_len;
         let is_connected = self.connected_ssid.trim() == ssid.trim();
 
         if !is_long {
-            ssid.clone()
+            ssid.to_string()
         } else {
             let short: String = ssid.chars().take(max_ssid_len).collect();
             if is_connected {
@@ -502,160 +892,589 @@ impl NetConnect {
 
     // ====== nmcli-related Commands ======
 
-    fn connect_to_wifi(&self, ssid: &str, password: &str) -> String {
-        if password == "" {
-            return String::new();
+    /// Run the connect on a background thread; `tick` picks up the result.
+    fn start_connect(&mut self, ssid: String, request: ConnectRequest) {
+        let (result_tx, result_rx) = mpsc::channel();
+        let backend = self.backend.clone();
+        let thread_ssid = ssid.clone();
+
+        std::thread::spawn(move || {
+            let result = match request {
+                ConnectRequest::Password(password) => backend.connect(&thread_ssid, &password),
+                ConnectRequest::Open => backend.connect_open(&thread_ssid),
+                ConnectRequest::Saved(uuid) => backend.connect_saved(&uuid),
+                ConnectRequest::Hidden(security, password) => {
+                    backend.connect_hidden(&thread_ssid, security, &password)
+                }
+            };
+            let _ = result_tx.send(result);
+        });
+
+        self.outcome = None;
+        self.connecting = Some(Connecting {
+            ssid,
+            started: Instant::now(),
+            result_rx,
+        });
+    }
+
+    fn disconnect(&mut self) {
+        if self.pending_action.is_some() {
+            return;
         }
 
-        let result = Command::new("nmcli")
-            .args(["device", "wifi", "connect", ssid, "password", password])
-            .output();
+        let action = WifiAction::Disconnect(self.connected_ssid.clone());
+        self.outcome = None;
+        self.start_action(action, |backend| {
+            backend.disconnect_wifi().map(|()| ActionResult::Done)
+        });
+    }
 
-        match result {
-            Ok(output) if output.status.success() => format!("Connected to {}", ssid),
-            Ok(output) => {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                format!("Failed to connect to {}: {}", ssid, stderr.trim())
-            }
-            Err(e) => format!("Failed to execute nmcli: {}", e),
+    fn toggle_radio(&mut self) {
+        if self.pending_action.is_some() {
+            return;
         }
+
+        let on = !self.radio_on;
+        self.outcome = None;
+        self.start_action(WifiAction::Radio(on), move |backend| {
+            backend.set_wifi_radio(on).map(|()| ActionResult::Done)
+        });
     }
 
-    fn make_wifi_list() -> Vec<(String, u8)> {
-        let output = Command::new("nmcli")
-            .args(&["-t", "-f", "SSID,SIGNAL", "dev", "wifi"])
-            .output()
-            .expect("failed to execute nmcli");
+    /// Run a change to a saved connection on a background thread and read the
+    /// saved connections again once it is done.
+    fn change_saved(
+        &mut self,
+        change: impl FnOnce(&dyn NetworkBackend) -> Result<()> + Send + 'static,
+    ) {
+        self.start_action(WifiAction::ChangeSaved, |backend| {
+            change(backend)?;
+            backend.saved_connections().map(ActionResult::Saved)
+        });
+    }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+    /// Run a disconnect, radio toggle or saved connection change on a
+    /// background thread, one at a time; `tick` picks up the result.
+    fn start_action(
+        &mut self,
+        action: WifiAction,
+        run: impl FnOnce(&dyn NetworkBackend) -> Result<ActionResult> + Send + 'static,
+    ) {
+        if self.pending_action.is_some() {
+            return;
+        }
 
-        let mut networks: Vec<(String, u8)> = stdout
-            .lines()
-            .filter_map(|line| {
-                let mut parts = line.splitn(2, ':');
-                let ssid = parts.next()?.trim();
-                let signal_str = parts.next()?.trim();
-                let signal = signal_str.parse::<u8>().ok()?;
-                if ssid.is_empty() {
-                    None
-                } else {
-                    Some((ssid.to_string(), signal))
-                }
-            })
-            .collect();
+        let (result_tx, result_rx) = mpsc::channel();
+        let backend = self.backend.clone();
 
-        networks.sort_by(|a, b| b.1.cmp(&a.1));
-        let mut seen_signals = HashSet::new();
-        networks
-            .into_iter()
-            .filter(|(_, signal)| seen_signals.insert(*signal))
-            .take(10)
-            .collect()
+        std::thread::spawn(move || {
+            let _ = result_tx.send(run(backend.as_ref()));
+        });
+
+        self.pending_action = Some(PendingAction { action, result_rx });
     }
 
-    fn get_connected_ssid(&self) -> String {
-        let output = Command::new("nmcli")
-            .args(&["-t", "-f", "active,ssid", "dev", "wifi"])
-            .output();
+    /// Rescan on a background thread and read the list again once it is done;
+    /// `tick` picks up the networks found.
+    fn start_rescan(&mut self) {
+        if self.scanning.is_some() || !self.radio_on {
+            return;
+        }
+
+        let (result_tx, result_rx) = mpsc::channel();
+        let backend = self.backend.clone();
+        let saved = self.saved.clone();
+        let ssids = self.ssids.clone();
+
+        std::thread::spawn(move || {
+            let result = backend
+                .rescan()
+                .and_then(|()| backend.wifi_list(&saved, &ssids));
+            let _ = result_tx.send(result);
+        });
+
+        self.outcome = None;
+        self.scanning = Some(Scanning {
+            started: Instant::now(),
+            result_rx,
+        });
+    }
 
-        let output = match output {
-            Ok(o) => o,
-            Err(_) => return String::new(),
+    fn toggle_autoconnect(&mut self) {
+        let Some(connection) = self.saved.get(self.selected_saved) else {
+            return;
         };
 
-        if !output.status.success() {
-            return String::new();
+        let uuid = connection.uuid.clone();
+        let autoconnect = !connection.autoconnect;
+        self.change_saved(move |backend| backend.set_autoconnect(&uuid, autoconnect));
+    }
+
+    fn accept_saved_prompt(&mut self) {
+        if self.pending_action.is_some() {
+            return;
+        }
+
+        match self.saved_prompt.take() {
+            Some(SavedPrompt::Forget { uuid, .. }) => {
+                self.change_saved(move |backend| backend.forget(&uuid));
+            }
+            Some(SavedPrompt::Priority { uuid, name, input }) => match input.parse::<i32>() {
+                Ok(priority) => {
+                    self.change_saved(move |backend| backend.set_priority(&uuid, priority));
+                }
+                Err(_) => {
+                    // keep the prompt open until a number is typed
+                    self.saved_prompt = Some(SavedPrompt::Priority { uuid, name, input });
+                }
+            },
+            None => {}
         }
+    }
+
+    /// Turn a finished connect into its outcome message.
+    fn check_connecting(&mut self) {
+        let Some(connecting) = &self.connecting else {
+            return;
+        };
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let result = match connecting.result_rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Err(eyre!("the connect thread stopped without a result"))
+            }
+        };
+
+        let ssid = &connecting.ssid;
+        self.outcome = Some(match result {
+            Ok(()) => ActionOutcome::Succeeded(format!("Connected to {}", ssid)),
+            Err(e) => ActionOutcome::Failed(format!("Failed to connect to {}: {}", ssid, e)),
+        });
+        self.connecting = None;
+    }
+
+    /// Turn a finished disconnect or radio toggle into its outcome message, or
+    /// show the saved connections after a finished change.
+    fn check_pending_action(&mut self) {
+        let Some(pending) = &self.pending_action else {
+            return;
+        };
+
+        let result = match pending.result_rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Err(eyre!("the nmcli thread stopped without a result"))
+            }
+        };
 
-        for line in stdout.lines() {
-            let mut parts = line.splitn(2, ':');
-            if let (Some(active), Some(ssid)) = (parts.next(), parts.next()) {
-                if active == "yes" {
-                    return ssid.to_string();
+        let Some(pending) = self.pending_action.take() else {
+            return;
+        };
+        match pending.action {
+            // saved connection changes report on the saved tab, not as an outcome
+            WifiAction::ChangeSaved => {
+                match result {
+                    Ok(ActionResult::Saved(saved)) => {
+                        self.saved = saved;
+                        self.saved_status.clear();
+                    }
+                    Ok(ActionResult::Done) => {}
+                    Err(e) => self.saved_status = e.to_string(),
                 }
+                self.selected_saved = self.selected_saved.min(self.saved.len().saturating_sub(1));
+            }
+            WifiAction::Disconnect(ssid) => {
+                self.outcome = Some(match result {
+                    Ok(_) => {
+                        self.connected_ssid.clear();
+                        if ssid.is_empty() {
+                            ActionOutcome::Succeeded("Disconnected".to_string())
+                        } else {
+                            ActionOutcome::Succeeded(format!("Disconnected from {}", ssid))
+                        }
+                    }
+                    Err(e) => ActionOutcome::Failed(format!("Failed to disconnect: {}", e)),
+                });
+            }
+            WifiAction::Radio(on) => {
+                self.outcome = Some(match result {
+                    Ok(_) => {
+                        self.radio_on = on;
+                        if !on {
+                            self.wifi_list.clear();
+                            self.connected_ssid.clear();
+                            self.scanning = None;
+                        }
+                        ActionOutcome::Succeeded(format!(
+                            "Wifi turned {}",
+                            if on { "on" } else { "off" }
+                        ))
+                    }
+                    Err(e) => ActionOutcome::Failed(format!("Failed to switch wifi: {}", e)),
+                });
             }
         }
+    }
 
-        String::new()
+    /// Show the networks found by a finished rescan.
+    fn check_scanning(&mut self) {
+        let Some(scanning) = &self.scanning else {
+            return;
+        };
+
+        let result = match scanning.result_rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Err(eyre!("the scan thread stopped without a result"))
+            }
+        };
+
+        match result {
+            Ok(wifi_list) => {
+                self.wifi_list = wifi_list;
+                self.selected_ssid = self.selected_ssid.min(self.wifi_list.len());
+            }
+            Err(e) => self.outcome = Some(ActionOutcome::Failed(format!("Rescan failed: {}", e))),
+        }
+        self.scanning = None;
     }
 
-    fn get_connection_info(&self) -> Vec<String> {
-        let output = Command::new("nmcli").args(&["device", "show"]).output();
+    fn make_connection_info(backend: &dyn NetworkBackend) -> Vec<String> {
+        let devices = match backend.connection_info() {
+            Ok(devices) => devices,
+            Err(_) => return vec!["Failed to get connection info.".to_string()],
+        };
 
-        match output {
-            Ok(output) if output.status.success() => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
+        let separator = "⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽";
+        let mut results = Vec::new();
 
-                let field_map = [
-                    ("GENERAL.DEVICE", "Device"),
-                    ("GENERAL.TYPE", "Type"),
-                    ("GENERAL.CONNECTION", "Name"),
-                    ("IP4.ADDRESS", "IPv4"),
-                    ("IP4.GATEWAY", "IPv4 Gateway"),
-                    ("IP4.DNS", "DNS"),
-                ];
+        for device in devices {
+            results.push(format!("===> {} ({})", device.name, device.device_type));
+            results.push(separator.to_string());
 
-                let mut devices = Vec::new();
-                let mut current_block = Vec::new();
+            for (label, value) in device.fields {
+                results.push(format!("{} :  ", label));
+                results.push(value);
+                results.push(separator.to_string());
+            }
 
-                for line in stdout.lines() {
-                    if line.starts_with("GENERAL.DEVICE") && !current_block.is_empty() {
-                        devices.push(current_block.clone());
-                        current_block.clear();
-                    }
-                    current_block.push(line);
-                }
-                if !current_block.is_empty() {
-                    devices.push(current_block);
+            results.push(String::new());
+        }
+
+        results.push(String::new());
+        results.push(String::new());
+
+        results
+    }
+}
+
+impl MenuWidget for NetConnect {
+    fn title(&self) -> &str {
+        "Internet"
+    }
+
+    /// Handle input events (keys only).
+    fn handle_event(&mut self, event: &Event) -> Result<()> {
+        match event {
+            Event::Key(key_event) => {
+                self.handle_key_event(key_event);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Start background thread to refresh network list and connection state.
+    fn start(&mut self) -> Result<()> {
+        if self.refresh_rx.is_some() {
+            return Ok(());
+        }
+
+        let (tx, rx) = mpsc::channel();
+        self.refresh_rx = Some(rx);
+
+        let backend = self.backend.clone();
+        let ssids = self.ssids.clone();
+        std::thread::spawn(move || {
+            loop {
+                let saved = backend.saved_connections();
+                // without saved connections the list is still useful
+                let known = saved.as_deref().unwrap_or_default();
+                let refresh = NetRefresh {
+                    wifi_list: backend.wifi_list(known, &ssids),
+                    saved,
+                    // an unknown radio state still shows the list
+                    radio_on: backend.wifi_radio().unwrap_or(true),
+                    connected_ssid: backend.connected_ssid().unwrap_or_default(),
+                    connection_info: NetConnect::make_connection_info(backend.as_ref()),
+                };
+
+                if tx.send(refresh).is_err() {
+                    break;
                 }
+                sleep(Duration::from_secs(3));
+            }
+        });
 
-                let mut results = Vec::new();
+        Ok(())
+    }
 
-                for device in devices {
-                    let mut device_type = "";
-                    let mut device_name = "";
+    /// Build the main and overlay widgets for rendering.
+    fn render(&self, area: Rect) -> WiMenuItem<'static> {
+        let max_width = area.width.saturating_sub(4) as usize;
+        let border_lines = if CONFIG().themes.borders_on { 2 } else { 0 };
+        let height = area.height.saturating_sub(border_lines) as usize;
+
+        let list = match self.view {
+            NetView::Networks => self.make_wifi_widget_list(max_width),
+            NetView::Saved => self.make_saved_widget_list(max_width, height),
+        };
 
-                    for line in &device {
-                        if line.starts_with("GENERAL.TYPE:") {
-                            device_type = line.splitn(2, ':').nth(1).unwrap_or("").trim();
-                        }
-                        if line.starts_with("GENERAL.DEVICE:") {
-                            device_name = line.splitn(2, ':').nth(1).unwrap_or("").trim();
-                        }
-                    }
+        let (overlay, overlay_area) = if let Some(connecting) = &self.connecting {
+            self.make_connecting_overlay(connecting, max_width, area)
+        } else if let Some(prompt) = &self.saved_prompt {
+            self.make_saved_prompt(prompt, max_width, area)
+        } else if let Some(connect_form) = &self.connect_form {
+            connect_form.form.render(max_width, area)
+        } else if self.show_info {
+            self.make_info_overlay(max_width, area)
+        } else {
+            self.make_empty_prompt()
+        };
 
-                    if device_type != "wifi" && device_type != "ethernet" {
-                        continue;
-                    }
+        WiMenuItem {
+            content: EnContentMenuItem::List(list),
+            overlay,
+            overlay_area,
+            show_overlay: self.connecting.is_some()
+                || self.saved_prompt.is_some()
+                || self.connect_form.is_some()
+                || self.show_info,
+        }
+    }
 
-                    results.push(format!("===> {} ({})", device_name, device_type));
-                    results.push("⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽".to_string());
-
-                    for (nmcli_field, label) in &field_map {
-                        for line in &device {
-                            if line.starts_with(nmcli_field) {
-                                let parts: Vec<&str> = line.splitn(2, ':').collect();
-                                if parts.len() == 2 {
-                                    let value = parts[1].trim();
-                                    results.push(format!("{} :  ", label));
-                                    results.push(value.to_string());
-                                    results.push("⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽⎽".to_string());
-                                }
-                            }
-                        }
-                    }
+    /// Apply the latest state sent by the refresh thread and pick up a
+    /// finished connect, rescan, disconnect or radio toggle.
+    fn tick(&mut self) {
+        self.check_connecting();
+        self.check_scanning();
+        self.check_pending_action();
 
-                    results.push(String::new());
+        let Some(rx) = &self.refresh_rx else {
+            return;
+        };
+
+        if let Some(refresh) = rx.try_iter().last() {
+            match refresh.wifi_list {
+                Ok(wifi_list) => {
+                    self.wifi_list = wifi_list;
+                    self.status.clear();
+                }
+                Err(e) => {
+                    self.wifi_list.clear();
+                    self.status = format!("Failed to list networks: {}", e);
+                }
+            }
+            match refresh.saved {
+                Ok(saved) => {
+                    self.saved = saved;
+                    self.saved_status.clear();
+                }
+                Err(e) => {
+                    self.saved.clear();
+                    self.saved_status = format!("Failed to list saved connections: {}", e);
+                }
+            }
+            self.selected_saved = self.selected_saved.min(self.saved.len().saturating_sub(1));
+            self.radio_on = refresh.radio_on;
+            if !self.radio_on {
+                // nmcli may still list networks seen just before the radio went off
+                self.wifi_list.clear();
+            }
+            self.loading = false;
+            self.connected_ssid = refresh.connected_ssid;
+            self.connection_info = refresh.connection_info;
+            self.selected_ssid = self.selected_ssid.min(self.wifi_list.len());
+        }
+    }
+
+    fn captures_input(&self) -> bool {
+        self.connect_form.is_some()
+            || matches!(self.saved_prompt, Some(SavedPrompt::Priority { .. }))
+    }
+}
+
+/// Splits `text` into lines of at most `width` characters, breaking at spaces
+/// where possible.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word: String = word.to_string();
+        while !word.is_empty() {
+            let line_len = line.chars().count();
+            let word_len = word.chars().count();
+            let needed = if line.is_empty() {
+                word_len
+            } else {
+                line_len + 1 + word_len
+            };
+
+            if needed <= width {
+                if !line.is_empty() {
+                    line.push(' ');
                 }
+                line.push_str(&word);
+                word.clear();
+            } else if line.is_empty() {
+                // a word longer than the whole line is cut
+                line = word.chars().take(width).collect();
+                word = word.chars().skip(width).collect();
+                lines.push(std::mem::take(&mut line));
+            } else {
+                lines.push(std::mem::take(&mut line));
+            }
+        }
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
 
-                results.push(String::new());
-                results.push(String::new());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::network::FakeNmcliBackend;
+    use std::thread::sleep;
+
+    fn net_connect(fixtures: &str) -> NetConnect {
+        NetConnect::with_backend(Arc::new(FakeNmcliBackend::parse(fixtures)))
+    }
 
-                results
+    /// Tick until the background action has finished.
+    fn finish_action(net: &mut NetConnect) {
+        for _ in 0..100 {
+            net.tick();
+            if net.pending_action.is_none() {
+                return;
             }
-            _ => vec!["Failed to get connection info.".to_string()],
+            sleep(Duration::from_millis(10));
         }
+        panic!("the action did not finish");
+    }
+
+    fn outcome(net: &NetConnect) -> Result<&str, &str> {
+        match net.outcome.as_ref().expect("an outcome") {
+            ActionOutcome::Succeeded(text) => Ok(text),
+            ActionOutcome::Failed(text) => Err(text),
+        }
+    }
+
+    #[test]
+    fn disconnect_runs_in_the_background() {
+        let mut net = net_connect(
+            "$ nmcli -t -f DEVICE,TYPE,STATE device\n\
+             wlan0:wifi:connected\n\
+             $ nmcli device disconnect wlan0\n",
+        );
+        net.connected_ssid = "HomeNet".to_string();
+
+        net.disconnect();
+        assert!(net.pending_action.is_some());
+        finish_action(&mut net);
+
+        assert_eq!(outcome(&net), Ok("Disconnected from HomeNet"));
+        assert!(net.connected_ssid.is_empty());
+    }
+
+    #[test]
+    fn failed_radio_toggle_keeps_the_radio_state() {
+        let mut net = net_connect(
+            "$ nmcli radio wifi off\n\
+             ! Error: Not authorized to control networking.\n",
+        );
+
+        net.toggle_radio();
+        finish_action(&mut net);
+
+        assert_eq!(
+            outcome(&net),
+            Err("Failed to switch wifi: Error: Not authorized to control networking.")
+        );
+        assert!(net.radio_on);
+    }
+
+    #[test]
+    fn radio_off_clears_the_network_list() {
+        let mut net = net_connect("$ nmcli radio wifi *\n");
+        net.wifi_list = vec![WifiNetwork {
+            ssid: "HomeNet".to_string(),
+            signal: 80,
+            security: "WPA2".to_string(),
+            uuid: None,
+        }];
+
+        net.toggle_radio();
+        // a second toggle while the first one runs is ignored
+        net.toggle_radio();
+        finish_action(&mut net);
+
+        assert_eq!(outcome(&net), Ok("Wifi turned off"));
+        assert!(!net.radio_on);
+        assert!(net.wifi_list.is_empty());
+    }
+
+    #[test]
+    fn forget_reads_the_saved_connections_again() {
+        let mut net = net_connect(
+            "$ nmcli connection delete uuid 2\n\
+             Connection 'Cafe' (2) successfully deleted.\n\
+             $ nmcli -t -f NAME,UUID,TYPE,TIMESTAMP,AUTOCONNECT,AUTOCONNECT-PRIORITY connection show\n\
+             HomeNet:1:802-11-wireless:1791970320:yes:10\n",
+        );
+        net.saved_prompt = Some(SavedPrompt::Forget {
+            uuid: "2".to_string(),
+            name: "Cafe".to_string(),
+        });
+        net.selected_saved = 1;
+
+        net.accept_saved_prompt();
+        assert!(net.pending_action.is_some());
+        finish_action(&mut net);
+
+        let names: Vec<&str> = net.saved.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["HomeNet"]);
+        assert_eq!(net.selected_saved, 0);
+        assert!(net.saved_status.is_empty());
+        assert!(net.outcome.is_none());
+    }
+
+    #[test]
+    fn failed_priority_change_shows_why() {
+        let mut net = net_connect(
+            "$ nmcli connection modify uuid 1 connection.autoconnect-priority 5\n\
+             ! Error: Not authorized to modify connections.\n",
+        );
+        net.saved_prompt = Some(SavedPrompt::Priority {
+            uuid: "1".to_string(),
+            name: "HomeNet".to_string(),
+            input: "5".to_string(),
+        });
+
+        net.accept_saved_prompt();
+        finish_action(&mut net);
+
+        assert_eq!(
+            net.saved_status,
+            "Error: Not authorized to modify connections."
+        );
     }
 }
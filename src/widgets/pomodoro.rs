@@ -0,0 +1,533 @@
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDate};
+use color_eyre::Result;
+use crossterm::event::{Event, KeyEvent, KeyEventKind};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Padding, Paragraph},
+};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    time::Duration,
+};
+
+use crate::{
+    alert,
+    config::{CONFIG, PomodoroConfig, aex_config_dir},
+    widgets::{
+        clock_font::pick_font,
+        content_menu::{EnContentMenuItem, MenuWidget, WiMenuItem, overlay_block, overlay_rect},
+        timers::{RunClock, format_duration},
+    },
+};
+
+/// Days shown in the weekly summary.
+const SUMMARY_DAYS: i64 = 7;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Phase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl Phase {
+    fn title(&self) -> &'static str {
+        match self {
+            Phase::Work => "Work",
+            Phase::ShortBreak => "Short break",
+            Phase::LongBreak => "Long break",
+        }
+    }
+
+    fn duration(&self) -> Duration {
+        let pomodoro = &CONFIG().pomodoro;
+        let minutes = match self {
+            Phase::Work => pomodoro.work_minutes,
+            Phase::ShortBreak => pomodoro.short_break_minutes,
+            Phase::LongBreak => pomodoro.long_break_minutes,
+        };
+        Duration::from_secs(minutes as u64 * 60)
+    }
+}
+
+/// A finished work session from the history file.
+#[derive(Clone)]
+struct Session {
+    finished_at: DateTime<Local>,
+    minutes: u32,
+}
+
+/// Pomodoro panel: counts down work and break phases in big digits and keeps
+/// a history of finished work sessions in `pomodoro_history.csv`.
+#[derive(Clone)]
+pub struct Pomodoro {
+    phase: Phase,
+    clock: RunClock,
+    /// work sessions finished in the current cycle
+    sessions_in_cycle: u32,
+    history: Vec<Session>,
+    show_summary: bool,
+    status: String,
+}
+
+impl Pomodoro {
+    // ====== Initialization ======
+
+    /// Create the panel and read the session history; a missing file means no history.
+    pub fn new() -> Self {
+        let (history, status) = match Self::read_history() {
+            Ok(history) => (history, String::new()),
+            Err(e) => (Vec::new(), format!("Failed to read history: {}", e)),
+        };
+
+        Self {
+            phase: Phase::Work,
+            clock: RunClock::default(),
+            sessions_in_cycle: 0,
+            history,
+            show_summary: false,
+            status,
+        }
+    }
+
+    // ====== Public Interface Methods ======
+
+    /// Move to the next phase once the current one ran out. Called from the main loop tick.
+    pub fn check_phase(&mut self) {
+        if !self.clock.is_running() || self.remaining() > Duration::ZERO {
+            return;
+        }
+
+        let finished = self.phase;
+        if let Some(session) = self.finish_phase(Local::now(), &CONFIG().pomodoro) {
+            self.save_session(&session);
+        }
+
+        alert::raise(&format!(
+            "{} finished, next: {}",
+            finished.title(),
+            self.phase.title()
+        ));
+    }
+
+    // ====== Input Handling ======
+
+    fn handle_key_event(&mut self, key_event: &KeyEvent) {
+        if key_event.kind != KeyEventKind::Press {
+            return;
+        }
+
+        let c = CONFIG();
+
+        if self.show_summary {
+            if c.key_matches(key_event, &c.keybinds.accept)
+                || c.key_matches(key_event, &c.keybinds.cancel)
+                || c.key_matches(key_event, &c.keybinds.info)
+            {
+                self.show_summary = false;
+            }
+
+            return;
+        }
+
+        if c.key_matches(key_event, &c.keybinds.accept) {
+            self.clock.toggle();
+        } else if c.key_matches(key_event, &c.keybinds.content_left) {
+            self.clock.reset();
+        } else if c.key_matches(key_event, &c.keybinds.content_right) {
+            self.advance_phase(c.pomodoro.sessions_per_cycle);
+        } else if c.key_matches(key_event, &c.keybinds.info) {
+            self.show_summary = true;
+        }
+    }
+
+    fn remaining(&self) -> Duration {
+        self.phase.duration().saturating_sub(self.clock.elapsed())
+    }
+
+    /// End the current phase at `now` and switch to the following one. A
+    /// finished work phase is added to the history and returned.
+    fn finish_phase(&mut self, now: DateTime<Local>, pomodoro: &PomodoroConfig) -> Option<Session> {
+        let session = (self.phase == Phase::Work).then_some(Session {
+            finished_at: now,
+            minutes: pomodoro.work_minutes,
+        });
+        if let Some(session) = &session {
+            self.history.push(session.clone());
+            self.sessions_in_cycle += 1;
+        }
+
+        self.advance_phase(pomodoro.sessions_per_cycle);
+        session
+    }
+
+    /// Switch to the following phase; it waits for `accept` to start. Skipping
+    /// a work phase this way does not count it as a session.
+    fn advance_phase(&mut self, sessions_per_cycle: u32) {
+        self.phase = match self.phase {
+            Phase::Work if self.sessions_in_cycle >= sessions_per_cycle => Phase::LongBreak,
+            Phase::Work => Phase::ShortBreak,
+            Phase::LongBreak => {
+                self.sessions_in_cycle = 0;
+                Phase::Work
+            }
+            Phase::ShortBreak => Phase::Work,
+        };
+        self.clock.reset();
+    }
+
+    // ====== Rendering UI Components ======
+
+    fn make_timer_paragraph(&self, area: Rect) -> Paragraph<'static> {
+        let theme = &CONFIG().themes;
+        let info_lines = 4;
+
+        let time = format_duration(self.remaining());
+        let font = pick_font(
+            &time,
+            area.width.saturating_sub(4),
+            area.height.saturating_sub(2 + info_lines),
+        );
+
+        let mut lines = vec![Line::from("")];
+        lines.extend(font.render_text(&time).lines);
+        lines.push(Line::from(""));
+
+        let state = if self.clock.is_running() {
+            "running"
+        } else {
+            "paused"
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{} · {}", self.phase.title(), state),
+            Style::default()
+                .fg(theme.content_selected_color)
+                .add_modifier(Modifier::BOLD),
+        )));
+
+        let (today_count, _) = self.summary_since(Local::now().date_naive());
+        lines.push(Line::from(format!(
+            "Session {}/{} in this cycle · {} done today",
+            (self.sessions_in_cycle + 1).min(CONFIG().pomodoro.sessions_per_cycle),
+            CONFIG().pomodoro.sessions_per_cycle,
+            today_count
+        )));
+
+        if !self.status.is_empty() {
+            lines.push(Line::from(Span::styled(
+                self.status.clone(),
+                Style::default().fg(Color::Red),
+            )));
+        }
+
+        let borders = if theme.borders_on {
+            Borders::ALL
+        } else {
+            Borders::NONE
+        };
+
+        Paragraph::new(Text::from(lines))
+            .style(Style::default().fg(theme.fg_color))
+            .centered()
+            .block(
+                Block::default()
+                    .borders(borders)
+                    .border_type(theme.border_type)
+                    .border_style(Style::default().fg(theme.border_color))
+                    .padding(Padding {
+                        left: 1,
+                        right: 1,
+                        top: 0,
+                        bottom: 0,
+                    }),
+            )
+    }
+
+    fn make_summary_overlay(
+        &self,
+        max_width: usize,
+        area: Rect,
+    ) -> (EnContentMenuItem<'static>, Rect) {
+        let theme = &CONFIG().themes;
+        let today = Local::now().date_naive();
+        let week_start =
+            today - ChronoDuration::days(today.weekday().num_days_from_monday() as i64);
+
+        let (today_count, today_minutes) = self.summary_since(today);
+        let (week_count, week_minutes) = self.summary_since(week_start);
+
+        let mut lines = vec![
+            Line::from(format!(
+                "Today      {:>3} sessions  {:>5} min",
+                today_count, today_minutes
+            )),
+            Line::from(format!(
+                "This week  {:>3} sessions  {:>5} min",
+                week_count, week_minutes
+            )),
+            Line::from(""),
+        ];
+
+        let days: Vec<(NaiveDate, usize)> = (0..SUMMARY_DAYS)
+            .rev()
+            .map(|offset| {
+                let day = today - ChronoDuration::days(offset);
+                let count = self
+                    .history
+                    .iter()
+                    .filter(|s| s.finished_at.date_naive() == day)
+                    .count();
+                (day, count)
+            })
+            .collect();
+
+        let max_count = days
+            .iter()
+            .map(|(_, count)| *count)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let bar_width = max_width.saturating_sub(14);
+
+        for (day, count) in days {
+            let filled = count * bar_width / max_count;
+            lines.push(Line::from(vec![
+                Span::raw(format!("{} {:>2} ", day.format("%a %d"), count)),
+                Span::styled(
+                    "█".repeat(filled),
+                    Style::default().fg(theme.bar_filled_color),
+                ),
+            ]));
+        }
+
+        let paragraph = Paragraph::new(lines).block(overlay_block().title("Pomodoro summary"));
+
+        (
+            EnContentMenuItem::Paragraph(paragraph),
+            overlay_rect(area, max_width as u16, SUMMARY_DAYS as u16 + 5, 1),
+        )
+    }
+
+    fn make_empty_prompt(&self) -> (EnContentMenuItem<'static>, Rect) {
+        (
+            EnContentMenuItem::Paragraph(
+                Paragraph::new("").style(Style::default().bg(CONFIG().themes.bg_color)),
+            ),
+            Rect::default(),
+        )
+    }
+
+    /// Number of sessions and minutes worked since the start of `day`.
+    fn summary_since(&self, day: NaiveDate) -> (usize, u32) {
+        self.history
+            .iter()
+            .filter(|s| s.finished_at.date_naive() >= day)
+            .fold((0, 0), |(count, minutes), s| {
+                (count + 1, minutes + s.minutes)
+            })
+    }
+
+    // ====== History Storage ======
+
+    fn history_path() -> Result<PathBuf> {
+        let mut path = aex_config_dir()?;
+        path.push("pomodoro_history.csv");
+        Ok(path)
+    }
+
+    /// Reads `finished_at,minutes` lines; malformed lines are skipped.
+    fn read_history() -> Result<Vec<Session>> {
+        let path = Self::history_path()?;
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let history = fs::read_to_string(&path)?
+            .lines()
+            .filter_map(Self::parse_history_line)
+            .collect();
+
+        Ok(history)
+    }
+
+    fn parse_history_line(line: &str) -> Option<Session> {
+        let (finished_at, minutes) = line.split_once(',')?;
+        Some(Session {
+            finished_at: DateTime::parse_from_rfc3339(finished_at.trim())
+                .ok()?
+                .with_timezone(&Local),
+            minutes: minutes.trim().parse().ok()?,
+        })
+    }
+
+    fn history_line(session: &Session) -> String {
+        format!("{},{}", session.finished_at.to_rfc3339(), session.minutes)
+    }
+
+    fn save_session(&mut self, session: &Session) {
+        self.status = match Self::append_history(session) {
+            Ok(()) => String::new(),
+            Err(e) => format!("Failed to save history: {}", e),
+        };
+    }
+
+    fn append_history(session: &Session) -> Result<()> {
+        let path = Self::history_path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", Self::history_line(session))?;
+        Ok(())
+    }
+}
+
+impl MenuWidget for Pomodoro {
+    fn title(&self) -> &str {
+        "Pomodoro"
+    }
+
+    /// Handle input events (keys only).
+    fn handle_event(&mut self, event: &Event) -> Result<()> {
+        match event {
+            Event::Key(key_event) => {
+                self.handle_key_event(key_event);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Build the main and overlay widgets for rendering.
+    fn render(&self, area: Rect) -> WiMenuItem<'static> {
+        let max_width = area.width.saturating_sub(4) as usize;
+
+        let paragraph = self.make_timer_paragraph(area);
+
+        let (overlay, overlay_area) = if self.show_summary {
+            self.make_summary_overlay(max_width, area)
+        } else {
+            self.make_empty_prompt()
+        };
+
+        WiMenuItem {
+            content: EnContentMenuItem::Paragraph(paragraph),
+            overlay,
+            overlay_area,
+            show_overlay: self.show_summary,
+        }
+    }
+
+    fn tick(&mut self) {
+        self.check_phase();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn pomodoro() -> Pomodoro {
+        Pomodoro {
+            phase: Phase::Work,
+            clock: RunClock::default(),
+            sessions_in_cycle: 0,
+            history: Vec::new(),
+            show_summary: false,
+            status: String::new(),
+        }
+    }
+
+    fn config(sessions_per_cycle: u32) -> PomodoroConfig {
+        PomodoroConfig {
+            sessions_per_cycle,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn long_break_follows_the_configured_number_of_sessions() {
+        let config = config(3);
+        let now = Local::now();
+        let mut pomodoro = pomodoro();
+
+        let mut phases = Vec::new();
+        for _ in 0..8 {
+            pomodoro.finish_phase(now, &config);
+            phases.push(pomodoro.phase.title());
+        }
+
+        assert_eq!(
+            phases,
+            [
+                "Short break",
+                "Work",
+                "Short break",
+                "Work",
+                "Long break",
+                "Work",
+                "Short break",
+                "Work",
+            ]
+        );
+        assert_eq!(pomodoro.history.len(), 4);
+        assert_eq!(pomodoro.sessions_in_cycle, 1);
+    }
+
+    #[test]
+    fn finished_work_is_recorded_with_its_length() {
+        let now = Local::now();
+        let mut pomodoro = pomodoro();
+
+        let session = pomodoro.finish_phase(now, &config(4)).unwrap();
+        assert_eq!(session.finished_at, now);
+        assert_eq!(session.minutes, 25);
+
+        // a finished break is not a session
+        assert!(pomodoro.finish_phase(now, &config(4)).is_none());
+        assert_eq!(pomodoro.history.len(), 1);
+    }
+
+    #[test]
+    fn skipping_or_cancelling_records_nothing() {
+        let mut pomodoro = pomodoro();
+
+        // cancel resets the running work phase
+        pomodoro.clock.start();
+        pomodoro.clock.reset();
+        assert_eq!(pomodoro.phase, Phase::Work);
+
+        // skipped work phases do not count towards the long break
+        for _ in 0..4 {
+            pomodoro.advance_phase(2);
+            assert_eq!(pomodoro.phase, Phase::ShortBreak);
+            pomodoro.advance_phase(2);
+        }
+
+        assert!(pomodoro.history.is_empty());
+        assert_eq!(pomodoro.sessions_in_cycle, 0);
+    }
+
+    #[test]
+    fn history_lines_round_trip_and_skip_malformed_ones() {
+        let session = Session {
+            finished_at: Local.with_ymd_and_hms(2026, 3, 14, 9, 30, 0).unwrap(),
+            minutes: 25,
+        };
+
+        let line = Pomodoro::history_line(&session);
+        let parsed = Pomodoro::parse_history_line(&line).unwrap();
+        assert_eq!(parsed.finished_at, session.finished_at);
+        assert_eq!(parsed.minutes, 25);
+
+        assert!(Pomodoro::parse_history_line("2026-03-14T09:30:00+01:00").is_none());
+        assert!(Pomodoro::parse_history_line("yesterday,25").is_none());
+        assert!(Pomodoro::parse_history_line("2026-03-14T09:30:00+01:00,lots").is_none());
+    }
+}
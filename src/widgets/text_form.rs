@@ -0,0 +1,331 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    config::CONFIG,
+    widgets::content_menu::{EnContentMenuItem, overlay_block, overlay_rect},
+};
+
+/// What a field takes and how its value is drawn.
+pub enum FieldKind {
+    Text,
+    /// drawn as `*`s
+    Secret,
+    /// one of a fixed list of options, picked with `content_left/right` or `toggle`
+    Choice(&'static [&'static str]),
+}
+
+pub struct FormField {
+    label: &'static str,
+    kind: FieldKind,
+    /// typed text of text and secret fields
+    value: String,
+    /// index of the picked option of a choice
+    choice: usize,
+}
+
+impl FormField {
+    pub fn text(label: &'static str) -> Self {
+        Self::new(label, FieldKind::Text)
+    }
+
+    pub fn secret(label: &'static str) -> Self {
+        Self::new(label, FieldKind::Secret)
+    }
+
+    pub fn choice(label: &'static str, options: &'static [&'static str]) -> Self {
+        Self::new(label, FieldKind::Choice(options))
+    }
+
+    fn new(label: &'static str, kind: FieldKind) -> Self {
+        Self {
+            label,
+            kind,
+            value: String::new(),
+            choice: 0,
+        }
+    }
+
+    /// The typed text, or the picked option of a choice.
+    pub fn value(&self) -> &str {
+        match self.kind {
+            FieldKind::Choice(options) => options[self.choice],
+            _ => &self.value,
+        }
+    }
+
+    /// The value as drawn: secrets masked, choices between arrows.
+    fn shown_value(&self) -> String {
+        match self.kind {
+            FieldKind::Text => self.value.clone(),
+            FieldKind::Secret => "*".repeat(self.value.chars().count()),
+            FieldKind::Choice(_) => format!("< {} >", self.value()),
+        }
+    }
+}
+
+/// Result of a key sent to a form.
+#[derive(Debug, PartialEq)]
+pub enum FormAction {
+    Editing,
+    Submit,
+    Cancel,
+}
+
+/// Overlay form with one or more fields; `info` and shift+tab move between
+/// the fields, `accept` submits and `cancel` closes it.
+pub struct TextForm {
+    title: String,
+    /// what `accept` does, shown in the hint line
+    submit_label: &'static str,
+    fields: Vec<FormField>,
+    focused: usize,
+    /// shown under the fields, e.g. why the form was not submitted
+    pub error: Option<String>,
+}
+
+impl TextForm {
+    pub fn new(
+        title: impl Into<String>,
+        submit_label: &'static str,
+        fields: Vec<FormField>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            submit_label,
+            fields,
+            focused: 0,
+            error: None,
+        }
+    }
+
+    /// Value of the field at `index`, in the order the fields were given.
+    pub fn value(&self, index: usize) -> &str {
+        self.fields.get(index).map_or("", FormField::value)
+    }
+
+    // ====== Input Handling ======
+
+    pub fn handle_key(&mut self, key_event: &KeyEvent) -> FormAction {
+        let c = CONFIG();
+
+        if c.key_matches(key_event, &c.keybinds.accept) {
+            return FormAction::Submit;
+        } else if c.key_matches(key_event, &c.keybinds.cancel) {
+            return FormAction::Cancel;
+        }
+
+        // the error is about the values before this edit
+        self.error = None;
+
+        if c.key_matches(key_event, &c.keybinds.info) {
+            self.focused = (self.focused + 1) % self.fields.len().max(1);
+            return FormAction::Editing;
+        } else if key_event.code == KeyCode::BackTab {
+            self.focused = (self.focused + self.fields.len().max(1) - 1) % self.fields.len().max(1);
+            return FormAction::Editing;
+        }
+
+        let Some(field) = self.fields.get_mut(self.focused) else {
+            return FormAction::Editing;
+        };
+
+        match field.kind {
+            FieldKind::Choice(options) => {
+                if c.key_matches(key_event, &c.keybinds.content_left) {
+                    field.choice = (field.choice + options.len() - 1) % options.len();
+                } else if c.key_matches(key_event, &c.keybinds.content_right)
+                    || c.key_matches(key_event, &c.keybinds.toggle)
+                {
+                    field.choice = (field.choice + 1) % options.len();
+                }
+            }
+            FieldKind::Text | FieldKind::Secret => match key_event.code {
+                KeyCode::Backspace => {
+                    field.value.pop();
+                }
+                KeyCode::Char(ch) => field.value.push(ch),
+                _ => {}
+            },
+        }
+
+        FormAction::Editing
+    }
+
+    // ====== Rendering UI Components ======
+
+    /// The form as an overlay near the top of `area`.
+    pub fn render(&self, max_width: usize, area: Rect) -> (EnContentMenuItem<'static>, Rect) {
+        let theme = &CONFIG().themes;
+        let keybinds = &CONFIG().keybinds;
+
+        let label_width = self
+            .fields
+            .iter()
+            .map(|field| field.label.chars().count())
+            .max()
+            .unwrap_or(0)
+            + 2;
+
+        let mut lines: Vec<Line> = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let focused = i == self.focused;
+                let label_style = if focused {
+                    Style::default().fg(theme.content_selected_color)
+                } else {
+                    Style::default().fg(theme.fg_color)
+                };
+
+                let value = field.shown_value();
+                let cursor = match field.kind {
+                    FieldKind::Choice(_) => "",
+                    _ if focused => "_",
+                    _ => "",
+                };
+
+                Line::from(vec![
+                    Span::styled(format!("{:<label_width$}", field.label), label_style),
+                    Span::raw(format!("{}{}", value, cursor)),
+                ])
+            })
+            .collect();
+
+        let hint = if self.fields.len() > 1 {
+            format!(
+                "{}: next field  {}: {}  {}: cancel",
+                keybinds.info, keybinds.accept, self.submit_label, keybinds.cancel
+            )
+        } else {
+            format!(
+                "{}: {}  {}: cancel",
+                keybinds.accept, self.submit_label, keybinds.cancel
+            )
+        };
+        lines.push(Line::from(Span::styled(
+            hint,
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        if let Some(error) = &self.error {
+            lines.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(Color::Red),
+            )));
+        }
+
+        let h = lines.len() as u16 + 2;
+        let paragraph = Paragraph::new(lines).block(overlay_block().title(self.title.clone()));
+
+        (
+            EnContentMenuItem::Paragraph(paragraph),
+            overlay_rect(area, max_width.saturating_sub(2) as u16, h, 3),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::init_test_config;
+    use crossterm::event::KeyModifiers;
+
+    const SECURITY: &[&str] = &["WPA", "WEP", "None"];
+
+    fn form() -> TextForm {
+        init_test_config();
+        TextForm::new(
+            "Hidden network",
+            "connect",
+            vec![
+                FormField::text("SSID"),
+                FormField::choice("Security", SECURITY),
+                FormField::secret("Password"),
+            ],
+        )
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(form: &mut TextForm, text: &str) {
+        for ch in text.chars() {
+            assert_eq!(
+                form.handle_key(&key(KeyCode::Char(ch))),
+                FormAction::Editing
+            );
+        }
+    }
+
+    #[test]
+    fn typing_and_backspace_edit_the_focused_field() {
+        let mut form = form();
+        type_text(&mut form, "cafe");
+        form.handle_key(&key(KeyCode::Backspace));
+        type_text(&mut form, "é");
+        assert_eq!(form.value(0), "café");
+        assert_eq!(form.value(2), "");
+        assert_eq!(form.value(3), "", "indices past the fields read as empty");
+    }
+
+    #[test]
+    fn secrets_are_masked() {
+        let mut form = form();
+        form.handle_key(&key(KeyCode::BackTab));
+        type_text(&mut form, "hunter2");
+        assert_eq!(form.value(2), "hunter2");
+        assert_eq!(form.fields[2].shown_value(), "*******");
+        assert_eq!(form.fields[1].shown_value(), "< WPA >");
+    }
+
+    #[test]
+    fn choices_cycle_both_ways_and_ignore_typing() {
+        let mut form = form();
+        form.handle_key(&key(KeyCode::Tab));
+
+        form.handle_key(&KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT));
+        assert_eq!(form.value(1), "None");
+        form.handle_key(&KeyEvent::new(KeyCode::Right, KeyModifiers::SHIFT));
+        form.handle_key(&key(KeyCode::Char(' ')));
+        assert_eq!(form.value(1), "WEP");
+
+        type_text(&mut form, "x");
+        assert_eq!(form.value(1), "WEP");
+        assert_eq!(form.value(0), "");
+    }
+
+    #[test]
+    fn tab_and_back_tab_move_between_fields_and_wrap() {
+        let mut form = form();
+        form.handle_key(&key(KeyCode::Tab));
+        form.handle_key(&key(KeyCode::Tab));
+        assert_eq!(form.focused, 2);
+        form.handle_key(&key(KeyCode::Tab));
+        assert_eq!(form.focused, 0);
+        form.handle_key(&key(KeyCode::BackTab));
+        assert_eq!(form.focused, 2);
+    }
+
+    #[test]
+    fn accept_and_cancel_end_editing() {
+        let mut form = form();
+        form.error = Some("SSID is empty".to_string());
+
+        // the error stays until the values change
+        assert_eq!(form.handle_key(&key(KeyCode::Enter)), FormAction::Submit);
+        assert!(form.error.is_some());
+        type_text(&mut form, "a");
+        assert!(form.error.is_none());
+
+        assert_eq!(form.handle_key(&key(KeyCode::Esc)), FormAction::Cancel);
+        assert_eq!(form.value(0), "a");
+    }
+}
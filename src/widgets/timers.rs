@@ -0,0 +1,554 @@
+use color_eyre::Result;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, Padding, Paragraph},
+};
+use std::time::{Duration, Instant};
+
+use crate::{
+    alert,
+    config::CONFIG,
+    widgets::content_menu::{
+        EnContentMenuItem, MenuWidget, WiMenuItem, overlay_block, overlay_rect,
+    },
+};
+
+/// Number of most recent laps shown under the stopwatch.
+const SHOWN_LAPS: usize = 5;
+
+/// Longest timer `parse_duration` accepts, 100 days; far enough below what
+/// `Instant` can add to stay clear of overflow.
+const MAX_DURATION_SECS: u64 = 100 * 24 * 3600;
+
+/// Time that only advances while running.
+#[derive(Clone, Default)]
+pub struct RunClock {
+    /// time accumulated before the current run
+    elapsed: Duration,
+    started: Option<Instant>,
+}
+
+impl RunClock {
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed + self.started.map(|s| s.elapsed()).unwrap_or_default()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    pub fn start(&mut self) {
+        if self.started.is_none() {
+            self.started = Some(Instant::now());
+        }
+    }
+
+    pub fn pause(&mut self) {
+        if let Some(started) = self.started.take() {
+            self.elapsed += started.elapsed();
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        if self.is_running() {
+            self.pause();
+        } else {
+            self.start();
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[derive(Clone)]
+struct Countdown {
+    name: String,
+    duration: Duration,
+    clock: RunClock,
+    finished: bool,
+}
+
+impl Countdown {
+    fn remaining(&self) -> Duration {
+        self.duration.saturating_sub(self.clock.elapsed())
+    }
+}
+
+#[derive(Clone, Default)]
+struct Stopwatch {
+    clock: RunClock,
+    /// total elapsed time at each lap
+    laps: Vec<Duration>,
+}
+
+/// Timers panel: a stopwatch with laps and any number of named countdown timers.
+///
+/// Rows are the stopwatch, then the timers, then "+ New timer".
+#[derive(Clone)]
+pub struct Timers {
+    stopwatch: Stopwatch,
+    timers: Vec<Countdown>,
+    selected: usize,
+    show_prompt: bool,
+    prompt_input: String,
+    prompt_error: String,
+}
+
+impl Timers {
+    // ====== Initialization ======
+
+    pub fn new() -> Self {
+        Self {
+            stopwatch: Stopwatch::default(),
+            timers: Vec::new(),
+            selected: 0,
+            show_prompt: false,
+            prompt_input: String::new(),
+            prompt_error: String::new(),
+        }
+    }
+
+    // ====== Public Interface Methods ======
+
+    /// Raise an alert for every countdown that ran out. Called from the main loop tick.
+    pub fn check_finished(&mut self) {
+        for timer in self.timers.iter_mut() {
+            if timer.finished || !timer.clock.is_running() || !timer.remaining().is_zero() {
+                continue;
+            }
+
+            timer.clock.pause();
+            timer.finished = true;
+            alert::raise_with_digits(&format!("{} is done", timer.name), "00:00");
+        }
+    }
+
+    // ====== Input Handling ======
+
+    fn handle_key_event(&mut self, key_event: &KeyEvent) {
+        if key_event.kind != KeyEventKind::Press {
+            return;
+        }
+
+        let c = CONFIG();
+
+        if self.show_prompt {
+            if c.key_matches(key_event, &c.keybinds.accept) {
+                self.accept_prompt();
+            } else if c.key_matches(key_event, &c.keybinds.cancel) {
+                self.close_prompt();
+            } else if key_event.code == KeyCode::Backspace {
+                self.prompt_input.pop();
+            } else if let KeyCode::Char(ch) = key_event.code {
+                self.prompt_input.push(ch);
+            }
+
+            return;
+        }
+
+        if c.key_matches(key_event, &c.keybinds.content_up) {
+            self.move_selected_up();
+        } else if c.key_matches(key_event, &c.keybinds.content_down) {
+            self.move_selected_down();
+        } else if c.key_matches(key_event, &c.keybinds.accept) {
+            self.start_pause_selected();
+        } else if c.key_matches(key_event, &c.keybinds.content_left) {
+            self.reset_selected();
+        } else if c.key_matches(key_event, &c.keybinds.content_right) {
+            self.lap_or_extend_selected();
+        } else if c.key_matches(key_event, &c.keybinds.delete) {
+            self.delete_selected();
+        }
+    }
+
+    fn rows(&self) -> usize {
+        self.timers.len() + 2
+    }
+
+    fn move_selected_down(&mut self) {
+        self.selected = (self.selected + 1) % self.rows();
+    }
+
+    fn move_selected_up(&mut self) {
+        self.selected = (self.selected + self.rows() - 1) % self.rows();
+    }
+
+    /// Index into `timers` of the selected row, if a timer is selected.
+    fn selected_timer(&self) -> Option<usize> {
+        (1..=self.timers.len())
+            .contains(&self.selected)
+            .then(|| self.selected - 1)
+    }
+
+    fn start_pause_selected(&mut self) {
+        if self.selected == 0 {
+            self.stopwatch.clock.toggle();
+        } else if let Some(index) = self.selected_timer() {
+            let timer = &mut self.timers[index];
+            if timer.finished {
+                timer.clock.reset();
+                timer.finished = false;
+            }
+            timer.clock.toggle();
+        } else {
+            self.open_prompt();
+        }
+    }
+
+    fn reset_selected(&mut self) {
+        if self.selected == 0 {
+            self.stopwatch = Stopwatch::default();
+        } else if let Some(index) = self.selected_timer() {
+            let timer = &mut self.timers[index];
+            timer.clock.reset();
+            timer.finished = false;
+        }
+    }
+
+    /// Records a lap on the running stopwatch, or adds a minute to a timer.
+    fn lap_or_extend_selected(&mut self) {
+        if self.selected == 0 {
+            if self.stopwatch.clock.is_running() {
+                let elapsed = self.stopwatch.clock.elapsed();
+                self.stopwatch.laps.push(elapsed);
+            }
+        } else if let Some(index) = self.selected_timer() {
+            let timer = &mut self.timers[index];
+            timer.duration += Duration::from_secs(60);
+            timer.finished = false;
+        }
+    }
+
+    fn delete_selected(&mut self) {
+        if let Some(index) = self.selected_timer() {
+            self.timers.remove(index);
+            self.selected = self.selected.min(self.rows() - 1);
+        }
+    }
+
+    fn open_prompt(&mut self) {
+        self.show_prompt = true;
+        self.prompt_input.clear();
+        self.prompt_error.clear();
+    }
+
+    fn close_prompt(&mut self) {
+        self.show_prompt = false;
+        self.prompt_input.clear();
+        self.prompt_error.clear();
+    }
+
+    /// Creates a timer from "<name> <duration>", e.g. "tea 4m" or "pasta 9:30".
+    fn accept_prompt(&mut self) {
+        let input = self.prompt_input.trim();
+        let (name, duration_str) = match input.rsplit_once(' ') {
+            Some((name, duration)) => (name.trim().to_string(), duration),
+            None => (String::new(), input),
+        };
+
+        let Some(duration) = parse_duration(duration_str) else {
+            self.prompt_error = format!("Invalid duration: {}", duration_str);
+            return;
+        };
+
+        let name = if name.is_empty() {
+            format!("Timer {}", self.timers.len() + 1)
+        } else {
+            name
+        };
+
+        let mut clock = RunClock::default();
+        clock.start();
+
+        self.timers.push(Countdown {
+            name,
+            duration,
+            clock,
+            finished: false,
+        });
+        self.selected = self.timers.len();
+        self.close_prompt();
+    }
+
+    // ====== Rendering UI Components ======
+
+    fn make_timer_widget_list(&self, max_width: usize) -> List<'static> {
+        let theme = &CONFIG().themes;
+        let row_style = |row: usize| {
+            if row == self.selected {
+                Style::default().fg(theme.content_selected_color)
+            } else {
+                Style::default().fg(theme.fg_color)
+            }
+        };
+
+        let mut items: Vec<Line> = Vec::new();
+
+        let stopwatch = &self.stopwatch;
+        items.push(
+            self.make_row_line(
+                "⏱ Stopwatch",
+                &format_duration(stopwatch.clock.elapsed()),
+                stopwatch.clock.is_running(),
+                max_width,
+            )
+            .style(row_style(0)),
+        );
+
+        let first_shown = stopwatch.laps.len().saturating_sub(SHOWN_LAPS);
+        for (i, lap) in stopwatch.laps.iter().enumerate().skip(first_shown) {
+            let previous = if i == 0 {
+                Duration::ZERO
+            } else {
+                stopwatch.laps[i - 1]
+            };
+            items.push(Line::from(Span::styled(
+                format!(
+                    "    lap {:<3} {}  (+{})",
+                    i + 1,
+                    format_duration(*lap),
+                    format_duration(*lap - previous)
+                ),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        items.push(Line::from(""));
+
+        for (i, timer) in self.timers.iter().enumerate() {
+            let status = if timer.finished {
+                "done".to_string()
+            } else {
+                format!(
+                    "{} / {}",
+                    format_duration(timer.remaining()),
+                    format_duration(timer.duration)
+                )
+            };
+
+            let mut line = self
+                .make_row_line(
+                    &format!("⏲ {}", timer.name),
+                    &status,
+                    timer.clock.is_running(),
+                    max_width,
+                )
+                .style(row_style(i + 1));
+
+            if timer.finished {
+                line = line.patch_style(Style::default().add_modifier(Modifier::BOLD));
+            }
+
+            items.push(line);
+        }
+
+        items.push(Line::from("+ New timer").style(row_style(self.rows() - 1)));
+
+        let block = Block::default()
+            .borders(if theme.borders_on {
+                Borders::ALL
+            } else {
+                Borders::NONE
+            })
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.border_color))
+            .padding(Padding {
+                left: 1,
+                right: 1,
+                top: 0,
+                bottom: 0,
+            });
+
+        List::new(items).block(block)
+    }
+
+    fn make_row_line(
+        &self,
+        name: &str,
+        status: &str,
+        running: bool,
+        max_width: usize,
+    ) -> Line<'static> {
+        let state = if running { "▶" } else { "⏸" };
+        let right = format!("{}  {}", status, state);
+
+        let name_width = max_width.saturating_sub(right.chars().count() + 1);
+        let name: String = name.chars().take(name_width).collect();
+
+        Line::from(format!("{:<name_width$} {}", name, right))
+    }
+
+    fn make_prompt(&self, max_width: usize, area: Rect) -> (EnContentMenuItem<'static>, Rect) {
+        let mut lines = vec![
+            Line::from(format!("{}_", self.prompt_input)),
+            Line::from(Span::styled(
+                "name and duration, e.g. \"tea 4m\" or \"pasta 9:30\"",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        if !self.prompt_error.is_empty() {
+            lines.push(Line::from(Span::styled(
+                self.prompt_error.clone(),
+                Style::default().fg(Color::Red),
+            )));
+        }
+
+        let paragraph = Paragraph::new(lines).block(overlay_block().title("New timer"));
+
+        (
+            EnContentMenuItem::Paragraph(paragraph),
+            overlay_rect(area, max_width as u16, 5, 3),
+        )
+    }
+
+    fn make_empty_prompt(&self) -> (EnContentMenuItem<'static>, Rect) {
+        (
+            EnContentMenuItem::Paragraph(
+                Paragraph::new("").style(Style::default().bg(CONFIG().themes.bg_color)),
+            ),
+            Rect::default(),
+        )
+    }
+}
+
+impl MenuWidget for Timers {
+    fn title(&self) -> &str {
+        "Timers"
+    }
+
+    /// Handle input events (keys only).
+    fn handle_event(&mut self, event: &Event) -> Result<()> {
+        match event {
+            Event::Key(key_event) => {
+                self.handle_key_event(key_event);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Build the main and overlay widgets for rendering.
+    fn render(&self, area: Rect) -> WiMenuItem<'static> {
+        let max_width = area.width.saturating_sub(4) as usize;
+
+        let list = self.make_timer_widget_list(max_width);
+
+        let (overlay, overlay_area) = if self.show_prompt {
+            self.make_prompt(max_width, area)
+        } else {
+            self.make_empty_prompt()
+        };
+
+        WiMenuItem {
+            content: EnContentMenuItem::List(list),
+            overlay,
+            overlay_area,
+            show_overlay: self.show_prompt,
+        }
+    }
+
+    fn tick(&mut self) {
+        self.check_finished();
+    }
+
+    fn captures_input(&self) -> bool {
+        self.show_prompt
+    }
+}
+
+/// Formats as MM:SS, or H:MM:SS from one hour on.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Parses "90s", "4m", "1h30m", "9:30" (minutes:seconds) or a plain number of minutes.
+///
+/// Durations of zero, above `MAX_DURATION_SECS` or too large to compute are rejected.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim().to_lowercase();
+    if s.is_empty() {
+        return None;
+    }
+
+    let secs = if s.contains(':') {
+        let parts: Vec<u64> = s
+            .split(':')
+            .map(|p| p.parse().ok())
+            .collect::<Option<_>>()?;
+        match parts[..] {
+            [m, sec] => m.checked_mul(60)?.checked_add(sec)?,
+            [h, m, sec] => h
+                .checked_mul(3600)?
+                .checked_add(m.checked_mul(60)?)?
+                .checked_add(sec)?,
+            _ => return None,
+        }
+    } else if let Ok(minutes) = s.parse::<u64>() {
+        minutes.checked_mul(60)?
+    } else {
+        let mut secs: u64 = 0;
+        let mut number = String::new();
+        for ch in s.chars() {
+            if ch.is_ascii_digit() {
+                number.push(ch);
+                continue;
+            }
+
+            let value: u64 = number.parse().ok()?;
+            number.clear();
+            let unit_secs = match ch {
+                'h' => value.checked_mul(3600)?,
+                'm' => value.checked_mul(60)?,
+                's' => value,
+                _ => return None,
+            };
+            secs = secs.checked_add(unit_secs)?;
+        }
+        if !number.is_empty() {
+            return None;
+        }
+        secs
+    };
+
+    (secs > 0 && secs <= MAX_DURATION_SECS).then(|| Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_all_formats() {
+        assert_eq!(parse_duration("90s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("4"), Some(Duration::from_secs(240)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("9:30"), Some(Duration::from_secs(570)));
+        assert_eq!(parse_duration("1:00:05"), Some(Duration::from_secs(3605)));
+        assert_eq!(parse_duration("0"), None);
+        assert_eq!(parse_duration("5x"), None);
+        assert_eq!(parse_duration("5m3"), None);
+    }
+
+    #[test]
+    fn rejects_overflowing_and_huge_durations() {
+        assert_eq!(parse_duration("99999999999999999h"), None);
+        assert_eq!(parse_duration("99999999999999999"), None);
+        assert_eq!(parse_duration("99999999999999999:00"), None);
+        assert_eq!(parse_duration("18446744073709551615s1s"), None);
+        assert_eq!(parse_duration("2401h"), None);
+        assert_eq!(
+            parse_duration("2400h"),
+            Some(Duration::from_secs(MAX_DURATION_SECS))
+        );
+    }
+}
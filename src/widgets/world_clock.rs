@@ -0,0 +1,254 @@
+use chrono::{DateTime, Local, Offset, TimeZone, Utc};
+use color_eyre::Result;
+use crossterm::event::Event;
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, Padding, Paragraph},
+};
+
+use crate::{
+    config::{CONFIG, WorldClockZone},
+    widgets::content_menu::{EnContentMenuItem, MenuWidget, WiMenuItem},
+};
+
+/// Shows the configured `[world_clock]` zones with their offset from local time.
+pub struct WorldClock;
+
+/// Current time of one zone, relative to local time.
+struct ZoneTime {
+    label: String,
+    time: String,
+    /// offset from local time in minutes
+    offset: i32,
+    /// calendar day difference from the local date (-1, 0 or +1)
+    day_diff: i64,
+}
+
+impl WorldClock {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Single line with every zone, shown under the big clock.
+    pub fn compact_line() -> Line<'static> {
+        let parts: Vec<String> = Self::zone_times()
+            .iter()
+            .map(|zone| {
+                format!(
+                    "{} {} {}{}",
+                    zone.label,
+                    zone.time,
+                    Self::format_offset(zone.offset),
+                    Self::format_day_diff(zone.day_diff)
+                )
+            })
+            .collect();
+
+        Line::from(parts.join("  │  ")).style(Style::default().fg(CONFIG().themes.fg_color))
+    }
+
+    // ====== Rendering UI Components ======
+
+    fn make_zone_widget_list(&self, max_width: usize) -> List<'static> {
+        let items: Vec<Line> = Self::zone_times()
+            .iter()
+            .map(|zone| self.make_zone_line(zone, max_width))
+            .collect();
+
+        let theme = &CONFIG().themes;
+
+        let block = Block::default()
+            .borders(if theme.borders_on {
+                Borders::ALL
+            } else {
+                Borders::NONE
+            })
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.border_color))
+            .padding(Padding {
+                left: 1,
+                right: 1,
+                top: 0,
+                bottom: 0,
+            });
+
+        List::new(items).block(block)
+    }
+
+    fn make_zone_line(&self, zone: &ZoneTime, max_width: usize) -> Line<'static> {
+        let right = format!(
+            "{:>6}{:>3}  {}",
+            Self::format_offset(zone.offset),
+            Self::format_day_diff(zone.day_diff),
+            zone.time
+        );
+
+        let label_width = max_width.saturating_sub(right.chars().count() + 1);
+        let label: String = zone.label.chars().take(label_width).collect();
+
+        Line::from(Span::styled(
+            format!("{:<label_width$} {}", label, right),
+            Style::default().fg(CONFIG().themes.fg_color),
+        ))
+    }
+
+    // ====== Time Zone Calculations ======
+
+    fn zone_times() -> Vec<ZoneTime> {
+        let now = Utc::now();
+        CONFIG()
+            .world_clock
+            .zones
+            .iter()
+            .map(|zone| Self::zone_time(zone, now, &Local))
+            .collect()
+    }
+
+    /// `local` is the machine's time zone outside of tests.
+    fn zone_time<L: TimeZone>(zone: &WorldClockZone, now: DateTime<Utc>, local: &L) -> ZoneTime {
+        let local = now.with_timezone(local);
+        let remote = now.with_timezone(&zone.tz);
+
+        let local_offset = local.offset().fix().local_minus_utc();
+        let remote_offset = remote.offset().fix().local_minus_utc();
+
+        let time_format = if CONFIG().clock.twelve_hour {
+            "%I:%M %p"
+        } else {
+            "%H:%M"
+        };
+
+        ZoneTime {
+            label: zone.label.clone(),
+            time: remote.format(time_format).to_string(),
+            offset: (remote_offset - local_offset) / 60,
+            day_diff: (remote.date_naive() - local.date_naive()).num_days(),
+        }
+    }
+
+    fn format_offset(minutes: i32) -> String {
+        if minutes == 0 {
+            return "±0h".to_string();
+        }
+
+        let sign = if minutes > 0 { '+' } else { '-' };
+        let minutes = minutes.abs();
+        if minutes % 60 == 0 {
+            format!("{}{}h", sign, minutes / 60)
+        } else {
+            format!("{}{}:{:02}h", sign, minutes / 60, minutes % 60)
+        }
+    }
+
+    fn format_day_diff(days: i64) -> String {
+        match days {
+            0 => String::new(),
+            d if d > 0 => format!(" +{}", d),
+            d => format!(" {}", d),
+        }
+    }
+}
+
+impl MenuWidget for WorldClock {
+    fn title(&self) -> &str {
+        "World Clock"
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> Result<()> {
+        Ok(())
+    }
+
+    /// Build the main and overlay widgets for rendering.
+    fn render(&self, area: Rect) -> WiMenuItem<'static> {
+        let max_width = area.width.saturating_sub(4) as usize;
+
+        let list = self.make_zone_widget_list(max_width);
+
+        WiMenuItem {
+            content: EnContentMenuItem::List(list),
+            overlay: EnContentMenuItem::Paragraph(Paragraph::new("")),
+            overlay_area: Rect::default(),
+            show_overlay: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::init_test_config;
+    use chrono_tz::{Asia, Europe, Pacific, Tz};
+
+    fn zone(tz: Tz) -> WorldClockZone {
+        WorldClockZone {
+            label: tz.name().to_string(),
+            tz,
+        }
+    }
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn half_and_quarter_hour_offsets() {
+        init_test_config();
+        let now = utc(2026, 1, 15, 12, 0);
+
+        let kolkata = WorldClock::zone_time(&zone(Asia::Kolkata), now, &Utc);
+        assert_eq!(kolkata.time, "17:30");
+        assert_eq!(kolkata.offset, 330);
+        assert_eq!(WorldClock::format_offset(kolkata.offset), "+5:30h");
+
+        let kathmandu = WorldClock::zone_time(&zone(Asia::Kathmandu), now, &Utc);
+        assert_eq!(kathmandu.time, "17:45");
+        assert_eq!(kathmandu.offset, 345);
+        assert_eq!(WorldClock::format_offset(kathmandu.offset), "+5:45h");
+    }
+
+    #[test]
+    fn offsets_are_relative_to_local_time() {
+        init_test_config();
+        let now = utc(2026, 1, 15, 12, 0);
+
+        // Kolkata seen from Kathmandu is 15 minutes behind
+        let kolkata = WorldClock::zone_time(&zone(Asia::Kolkata), now, &Asia::Kathmandu);
+        assert_eq!(kolkata.offset, -15);
+        assert_eq!(WorldClock::format_offset(kolkata.offset), "-0:15h");
+
+        let berlin = WorldClock::zone_time(&zone(Europe::Berlin), now, &Asia::Kolkata);
+        assert_eq!(berlin.offset, -270);
+        assert_eq!(WorldClock::format_offset(berlin.offset), "-4:30h");
+
+        let same = WorldClock::zone_time(&zone(Europe::Berlin), now, &Europe::Berlin);
+        assert_eq!(WorldClock::format_offset(same.offset), "±0h");
+        assert_eq!(WorldClock::format_offset(-300), "-5h");
+    }
+
+    #[test]
+    fn day_differences_across_the_date_line() {
+        init_test_config();
+        // 23:30 on the 15th in UTC is already the 16th in Kiritimati (UTC+14)
+        let now = utc(2026, 1, 15, 23, 30);
+
+        let kiritimati = WorldClock::zone_time(&zone(Pacific::Kiritimati), now, &Utc);
+        assert_eq!(kiritimati.time, "13:30");
+        assert_eq!(kiritimati.offset, 14 * 60);
+        assert_eq!(kiritimati.day_diff, 1);
+        assert_eq!(WorldClock::format_day_diff(kiritimati.day_diff), " +1");
+
+        let utc_zone = WorldClockZone {
+            label: "UTC".to_string(),
+            tz: Tz::UTC,
+        };
+        let behind = WorldClock::zone_time(&utc_zone, now, &Pacific::Kiritimati);
+        assert_eq!(behind.day_diff, -1);
+        assert_eq!(WorldClock::format_day_diff(behind.day_diff), " -1");
+
+        let kolkata = WorldClock::zone_time(&zone(Asia::Kolkata), utc(2026, 1, 15, 12, 0), &Utc);
+        assert_eq!(kolkata.day_diff, 0);
+        assert_eq!(WorldClock::format_day_diff(kolkata.day_diff), "");
+    }
+}
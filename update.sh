@@ -69,6 +69,37 @@ accept = "enter"
 info = "tab"
 cancel = "esc"
 quit = "q"
+toggle = "space"
+delete = "delete"
+solo = "s"
+profiles = "p"
+balance_modifier = "alt"
+fine_modifier = "ctrl"
+seek_modifier = "alt"
+disconnect = "d"
+radio = "w"
+rescan = "r"
+
+[clock]
+show_seconds = false
+twelve_hour = false
+show_date = false
+date_format = "%A, %d %B %Y"
+font = "auto"
+
+[alarms]
+snooze_minutes = 5
+
+[pomodoro]
+work_minutes = 25
+short_break_minutes = 5
+long_break_minutes = 15
+sessions_per_cycle = 4
+
+[audio]
+volume_step = 5
+fine_step = 1
+max_volume = 100
 
 EOF
 else